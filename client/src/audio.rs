@@ -7,42 +7,94 @@ use tokio::sync::mpsc;
 use std::time::SystemTime;
 use uuid::Uuid;
 
+use crate::resample::{Resampler, TARGET_SAMPLE_RATE};
+use crate::vad::{ClientVad, VadEvent};
+
+/// Trailing silence required, after speech has been detected, before a
+/// segment is considered ended (the VAD's hangover); the
+/// `silence_timeout_ms` countdown starts from that point.
+const HANGOVER_MS: u32 = 200;
+
+/// A span of audio the VAD judged to be speech, bounded by when it started
+/// and ended talking.
+#[derive(Debug, Clone, Copy)]
+pub struct SpeechSegment {
+    pub start: SystemTime,
+    pub end: SystemTime,
+}
+
+/// Endpointing events surfaced alongside the raw `AudioChunk` stream so a
+/// caller can react to automatic silence detection without parsing audio
+/// itself.
+#[derive(Debug, Clone, Copy)]
+pub enum EndpointEvent {
+    SpeechSegment(SpeechSegment),
+    /// The speaker has been silent for at least `silence_timeout_ms` since
+    /// their last speech segment ended - the caller should auto-stop the
+    /// recording the same way a manual `stop_recording` would.
+    SilenceTimeout,
+}
+
 pub struct AudioCapture {
     config: AudioConfig,
     session_id: Option<Uuid>,
     host: Host,
     device: Device,
+    stream: Option<cpal::Stream>,
 }
 
 impl AudioCapture {
     pub fn new(config: &AudioConfig) -> Result<Self> {
         let host = cpal::default_host();
-        
-        let device = if let Some(device_name) = &config.device {
-            // Try to find the specified device
-            host.input_devices()?
-                .find(|d| d.name().map(|n| n == *device_name).unwrap_or(false))
-                .context(format!("Audio device '{}' not found", device_name))?
-        } else {
-            // Use default device
-            host.default_input_device()
-                .context("No default input device available")?
-        };
-        
+        let device = Self::resolve_device(&host, config.device.as_deref())?;
+
         info!("Using audio device: {}", device.name().unwrap_or_else(|_| "Unknown".to_string()));
-        
+
         Ok(Self {
             config: config.clone(),
             session_id: None,
             host,
             device,
+            stream: None,
         })
     }
+
+    fn resolve_device(host: &Host, device_name: Option<&str>) -> Result<Device> {
+        if let Some(device_name) = device_name {
+            host.input_devices()?
+                .find(|d| d.name().map(|n| n == device_name).unwrap_or(false))
+                .context(format!("Audio device '{}' not found", device_name))
+        } else {
+            host.default_input_device()
+                .context("No default input device available")
+        }
+    }
+
+    /// Switches to a different input device, for when the user changes the
+    /// device setting without restarting the client. Tears down any active
+    /// stream first, since a `cpal::Stream` is bound to the device it was
+    /// built from and can't be repointed in place; recording must be
+    /// restarted by the caller to rebuild the stream against the new device.
+    pub fn set_device(&mut self, device_name: Option<&str>) -> Result<()> {
+        self.stream.take();
+        self.session_id = None;
+        self.device = Self::resolve_device(&self.host, device_name)?;
+        self.config.device = device_name.map(|s| s.to_string());
+        info!("Switched audio device to: {}", self.device.name().unwrap_or_else(|_| "Unknown".to_string()));
+        Ok(())
+    }
     
+    /// `vad_threshold` and `silence_timeout_ms` drive automatic endpointing:
+    /// once a speech segment has ended, `silence_timeout_ms` of continued
+    /// silence produces `EndpointEvent::SilenceTimeout` on the returned
+    /// channel so the caller can auto-stop hands-free instead of requiring
+    /// push-to-talk.
     pub fn start_recording(
         &mut self,
         session_id: Uuid,
-    ) -> Result<mpsc::Receiver<AudioChunk>> {
+        vad_threshold: f32,
+        silence_timeout_ms: u64,
+    ) -> Result<(mpsc::Receiver<AudioChunk>, mpsc::Receiver<EndpointEvent>)> {
         if self.session_id.is_some() {
             return Err(anyhow::anyhow!("Recording already active"));
         }
@@ -71,84 +123,127 @@ impl AudioCapture {
         info!("Audio config: {:?}", config);
         
         let (tx, rx) = mpsc::channel(32);
+        let (endpoint_tx, endpoint_rx) = mpsc::channel(8);
         let session_id_clone = session_id;
         let sample_rate_clone = sample_rate;
         let channels_clone = self.config.channels;
-        
+
         // Create the audio stream based on the sample format
         let stream = match supported_config.sample_format() {
             SampleFormat::I8 => {
-                self.create_stream::<i8>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<i8>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::I16 => {
-                self.create_stream::<i16>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<i16>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::I32 => {
-                self.create_stream::<i32>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<i32>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::I64 => {
-                self.create_stream::<i64>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<i64>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::U8 => {
-                self.create_stream::<u8>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<u8>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::U16 => {
-                self.create_stream::<u16>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<u16>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::U32 => {
-                self.create_stream::<u32>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<u32>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::U64 => {
-                self.create_stream::<u64>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<u64>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::F32 => {
-                self.create_stream::<f32>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<f32>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             SampleFormat::F64 => {
-                self.create_stream::<f64>(&config, tx, session_id_clone, sample_rate_clone, channels_clone)?
+                self.create_stream::<f64>(&config, tx, endpoint_tx, session_id_clone, sample_rate_clone, channels_clone, vad_threshold, silence_timeout_ms)?
             }
             _ => {
                 return Err(anyhow::anyhow!("Unsupported sample format: {:?}", supported_config.sample_format()));
             }
         };
-        
+
         stream.play()?;
-        
-        // Keep the stream alive by storing it
-        // The stream will be automatically dropped when the AudioCapture is dropped
-        std::mem::forget(stream);
-        
-        Ok(rx)
+
+        // Keep the stream handle so `stop_recording`/`set_device` can tear it
+        // down explicitly instead of leaking it for the life of the process.
+        self.stream = Some(stream);
+
+        Ok((rx, endpoint_rx))
     }
-    
+
     fn create_stream<T>(
         &self,
         config: &StreamConfig,
         tx: mpsc::Sender<AudioChunk>,
+        endpoint_tx: mpsc::Sender<EndpointEvent>,
         session_id: Uuid,
         sample_rate: u32,
         channels: u16,
+        vad_threshold: f32,
+        silence_timeout_ms: u64,
     ) -> Result<cpal::Stream>
     where
         T: cpal::Sample + cpal::SizedSample + Send + 'static,
         f32: cpal::FromSample<T>,
     {
+        let mut resampler = Resampler::new(sample_rate, channels);
+        let mut vad = ClientVad::new(TARGET_SAMPLE_RATE, vad_threshold, HANGOVER_MS);
+        let mut segment_start: Option<SystemTime> = None;
+        let mut silence_since: Option<SystemTime> = None;
+
         let stream = self.device.build_input_stream(
             config,
             move |data: &[T], _: &cpal::InputCallbackInfo| {
                 // Convert samples to f32
                 let f32_data: Vec<f32> = data.iter().map(|&sample| cpal::Sample::to_sample(sample)).collect();
-                
-                debug!("Captured {} audio samples", f32_data.len());
-                
+
+                // Downmix to mono and resample to the fixed 16kHz rate
+                // WhisperManager expects, regardless of what the device runs at.
+                let resampled = resampler.process(&f32_data);
+                if resampled.is_empty() {
+                    return;
+                }
+
+                let now = SystemTime::now();
+                match vad.push(&resampled) {
+                    VadEvent::SpeechStarted => {
+                        segment_start = Some(now);
+                        silence_since = None;
+                    }
+                    VadEvent::SpeechEnded => {
+                        if let Some(start) = segment_start.take() {
+                            let _ = endpoint_tx.try_send(EndpointEvent::SpeechSegment(SpeechSegment {
+                                start,
+                                end: now,
+                            }));
+                        }
+                        silence_since = Some(now);
+                    }
+                    VadEvent::None => {}
+                }
+
+                // Once we've seen at least one completed speech segment,
+                // auto-stop after the speaker stays silent long enough.
+                if let Some(since) = silence_since {
+                    if since.elapsed().unwrap_or_default().as_millis() as u64 >= silence_timeout_ms {
+                        let _ = endpoint_tx.try_send(EndpointEvent::SilenceTimeout);
+                        silence_since = None;
+                    }
+                }
+
+                debug!("Captured {} audio samples", resampled.len());
+
                 let chunk = AudioChunk {
                     session_id,
-                    data: f32_data,
-                    sample_rate,
-                    channels,
+                    data: resampled,
+                    sample_rate: TARGET_SAMPLE_RATE,
+                    channels: 1,
                     timestamp: SystemTime::now(),
                 };
-                
+
                 // Send chunk (non-blocking)
                 if let Err(e) = tx.try_send(chunk) {
                     warn!("Failed to send audio chunk: {}", e);
@@ -164,14 +259,17 @@ impl AudioCapture {
     }
     
     pub fn stop_recording(&mut self) {
+        // Dropping the stream stops capture at the OS level; a fresh one is
+        // built the next time `start_recording` runs.
+        self.stream.take();
         self.session_id = None;
         info!("Recording stopped");
     }
-    
+
     pub fn is_recording(&self) -> bool {
         self.session_id.is_some()
     }
-    
+
     pub fn list_input_devices() -> Result<Vec<String>> {
         let host = cpal::default_host();
         let devices: Result<Vec<String>, _> = host
@@ -180,4 +278,104 @@ impl AudioCapture {
             .collect();
         devices
     }
+
+    /// Like `list_input_devices`, but also surfaces each device's supported
+    /// sample rates and formats so a settings UI can warn about unsupported
+    /// configs before the user hits a `build_input_stream` error at record
+    /// time.
+    pub fn list_input_devices_with_info() -> Result<Vec<DeviceInfo>> {
+        let host = cpal::default_host();
+        let mut infos = Vec::new();
+
+        for device in host.input_devices()? {
+            let name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+            let mut sample_rates = Vec::new();
+            let mut sample_formats = Vec::new();
+
+            if let Ok(configs) = device.supported_input_configs() {
+                for cfg in configs {
+                    sample_rates.push(cfg.min_sample_rate().0);
+                    sample_rates.push(cfg.max_sample_rate().0);
+                    let format = format!("{:?}", cfg.sample_format());
+                    if !sample_formats.contains(&format) {
+                        sample_formats.push(format);
+                    }
+                }
+            }
+            sample_rates.sort_unstable();
+            sample_rates.dedup();
+
+            infos.push(DeviceInfo { name, sample_rates, sample_formats });
+        }
+
+        Ok(infos)
+    }
+}
+
+/// Supported sample rates/formats for one input device, as reported by cpal.
+#[derive(Debug, Clone)]
+pub struct DeviceInfo {
+    pub name: String,
+    pub sample_rates: Vec<u32>,
+    pub sample_formats: Vec<String>,
+}
+
+/// Encodes captured f32 PCM into 20ms Opus frames, batching them into
+/// `OpusChunk`s the same way `AudioCapture` batches raw PCM into `AudioChunk`s.
+/// Opus only accepts fixed frame sizes, so samples are buffered until a full
+/// 20ms frame is available; any leftover samples carry over to the next call.
+pub struct OpusFrameEncoder {
+    encoder: opus::Encoder,
+    sample_rate: u32,
+    channels: u16,
+    frame_samples: usize,
+    pending: Vec<f32>,
+}
+
+impl OpusFrameEncoder {
+    pub fn new(sample_rate: u32, channels: u16) -> Result<Self> {
+        let opus_channels = if channels == 1 { opus::Channels::Mono } else { opus::Channels::Stereo };
+        let encoder = opus::Encoder::new(sample_rate, opus_channels, opus::Application::Voip)
+            .context("Failed to create Opus encoder")?;
+
+        // 20ms is the frame size Opus recommends for voice; per-channel sample
+        // count scales with the configured sample rate.
+        let frame_samples = (sample_rate as usize / 50) * channels as usize;
+
+        Ok(Self {
+            encoder,
+            sample_rate,
+            channels,
+            frame_samples,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Buffer `data` and encode every complete 20ms frame it produces. Returns
+    /// zero or more Opus packets; callers batch these into an `OpusChunk` to
+    /// send over IPC.
+    pub fn push(&mut self, data: &[f32]) -> Result<Vec<Vec<u8>>> {
+        self.pending.extend_from_slice(data);
+
+        let mut frames = Vec::new();
+        let mut scratch = vec![0u8; 4000];
+
+        while self.pending.len() >= self.frame_samples {
+            let frame: Vec<f32> = self.pending.drain(..self.frame_samples).collect();
+            let len = self.encoder
+                .encode_float(&frame, &mut scratch)
+                .context("Failed to encode Opus frame")?;
+            frames.push(scratch[..len].to_vec());
+        }
+
+        Ok(frames)
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
 }
\ No newline at end of file
@@ -1,9 +1,18 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use log::{info, error, debug, warn};
 use shared::{Config, ClientMessage, DaemonMessage, protocol};
 use tokio::net::UnixStream;
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::time::{timeout, Duration};
+use uuid::Uuid;
+
+mod audio;
+mod resample;
+mod vad;
+
+use audio::{AudioCapture, EndpointEvent, OpusFrameEncoder};
+use resample::TARGET_SAMPLE_RATE;
 
 #[derive(Parser)]
 #[command(name = "dictation-client")]
@@ -11,24 +20,34 @@ use tokio::time::{timeout, Duration};
 struct Args {
     #[arg(short, long)]
     start: bool,
-    
+
     #[arg(long)]
     stop: bool,
-    
+
     #[arg(long)]
     status: bool,
+
+    /// Re-run transcription over the archived WAV for this session id
+    /// (requires `audio.save_recordings` to have been on when it recorded)
+    #[arg(long, value_name = "SESSION_ID")]
+    retranscribe: Option<Uuid>,
+
+    /// Tell the daemon to re-read its config file and apply settings that
+    /// support hot-reload, without restarting it
+    #[arg(long)]
+    reload_config: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     env_logger::init();
     let args = Args::parse();
-    
+
     info!("Dictation client started");
-    
+
     // Load configuration to get socket path
     let config = Config::load()?;
-    
+
     // Connect to daemon
     let mut stream = match UnixStream::connect(&config.ipc.socket_path).await {
         Ok(stream) => {
@@ -40,38 +59,44 @@ async fn main() -> Result<()> {
             return Err(e.into());
         }
     };
-    
+
     // Send appropriate command
     let message = if args.start {
         ClientMessage::StartRecording
     } else if args.stop {
-        ClientMessage::StopRecording
+        let session_id = active_session_id(&mut stream).await?;
+        ClientMessage::StopRecording(session_id)
     } else if args.status {
         ClientMessage::GetStatus
+    } else if let Some(session_id) = args.retranscribe {
+        ClientMessage::Retranscribe(session_id)
+    } else if args.reload_config {
+        ClientMessage::ReloadConfig
     } else {
-        error!("Please specify --start, --stop, or --status");
+        error!("Please specify --start, --stop, --status, --retranscribe, or --reload-config");
         return Ok(());
     };
-    
+
     // Send message to daemon
     protocol::send_message(&mut stream, &message).await?;
     info!("Sent message to daemon: {:?}", message);
-    
+
     // Receive response
     let response: DaemonMessage = protocol::receive_message(&mut stream).await?;
     info!("Received response: {:?}", response);
-    
+
     // Handle response
     match response {
         DaemonMessage::RecordingStarted(session_id) => {
             println!("✓ Recording started with session ID: {}", session_id);
 
-            // If this is a start recording command, listen for transcription updates
-            if args.start {
-                if let Err(e) = listen_for_transcription(&mut stream).await {
-                    error!("Failed to listen for transcription: {}", e);
-                    return Err(e);
-                }
+            // Capture the microphone and stream it to the daemon until the
+            // daemon tells us the recording finished (e.g. after
+            // `stream_audio` pushes a StopRecording once the capture side's
+            // VAD reports a silence timeout).
+            if let Err(e) = record_and_stream(stream, config, session_id).await {
+                error!("Failed to record and stream audio: {}", e);
+                return Err(e);
             }
         }
         DaemonMessage::RecordingStopped => {
@@ -92,22 +117,159 @@ async fn main() -> Result<()> {
         DaemonMessage::TranscriptionComplete(session) => {
             println!("✓ Transcription complete: {}", session.text);
         }
+        DaemonMessage::Retranscribed { session_id, text } => {
+            println!("✓ Retranscribed session {}: {}", session_id, text);
+        }
+        DaemonMessage::ConfigReloaded => {
+            println!("✓ Config reloaded");
+        }
         _ => {
             // Ignore other message types for this simple client
         }
     }
-    
+
+    Ok(())
+}
+
+/// `--stop` is a separate invocation from the `--start` that began the
+/// recording, so this CLI has no session ID of its own to send; ask the
+/// daemon which session is active and target that one.
+async fn active_session_id(stream: &mut UnixStream) -> Result<uuid::Uuid> {
+    protocol::send_message(stream, &ClientMessage::GetStatus).await?;
+    match protocol::receive_message::<_, DaemonMessage>(stream).await? {
+        DaemonMessage::Status(status) => status.active_sessions.first().copied()
+            .context("No active recording session to stop"),
+        other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+    }
+}
+
+/// Captures the microphone via `AudioCapture`, streams it to the daemon as
+/// `StreamAudio`/`StreamAudioOpus`, and prints transcription updates until the
+/// daemon reports the session is done. The capture side's endpointing
+/// (`EndpointEvent::SilenceTimeout`) auto-stops the recording hands-free,
+/// mirroring what a manual `--stop` would send.
+async fn record_and_stream(stream: UnixStream, config: Config, session_id: Uuid) -> Result<()> {
+    println!("🎤 Recording... Speak now!");
+
+    let (mut read_half, mut write_half) = stream.into_split();
+
+    // Ask whether the daemon understands Opus before committing to it, the
+    // same way StreamAudioOpus's doc comment requires.
+    let opus_supported = query_opus_supported(&mut read_half, &mut write_half).await?;
+
+    let mut capture = AudioCapture::new(&config.audio)?;
+    let (audio_rx, endpoint_rx) = capture.start_recording(
+        session_id,
+        config.whisper.vad_threshold,
+        config.whisper.silence_timeout_ms,
+    )?;
+
+    let send_task = tokio::spawn(stream_audio(
+        write_half,
+        audio_rx,
+        endpoint_rx,
+        session_id,
+        opus_supported,
+    ));
+
+    listen_for_transcription(read_half).await?;
+
+    capture.stop_recording();
+    send_task.abort();
+
     Ok(())
 }
 
-async fn listen_for_transcription(stream: &mut UnixStream) -> Result<()> {
-    println!("🎤 Recording... Speak now! (RealtimeSTT will handle audio capture)");
+/// `GetStatus` doubles as the opus negotiation handshake: the read half is
+/// borrowed just long enough to read the one response, before `listen_for_transcription`
+/// takes ownership of it for the rest of the session.
+async fn query_opus_supported(read_half: &mut OwnedReadHalf, write_half: &mut OwnedWriteHalf) -> Result<bool> {
+    protocol::send_message(write_half, &ClientMessage::GetStatus).await?;
+    match protocol::receive_message::<_, DaemonMessage>(read_half).await? {
+        DaemonMessage::Status(status) => Ok(status.opus_supported),
+        other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+    }
+}
+
+/// Owns `write_half` for the life of the recording: forwards captured audio
+/// chunks to the daemon (Opus-encoded when supported, raw PCM otherwise) and
+/// sends `StopRecording` itself once the capture side's VAD detects enough
+/// trailing silence, so a hands-free session doesn't need a second `--stop`
+/// invocation.
+async fn stream_audio(
+    mut write_half: OwnedWriteHalf,
+    mut audio_rx: tokio::sync::mpsc::Receiver<shared::AudioChunk>,
+    mut endpoint_rx: tokio::sync::mpsc::Receiver<EndpointEvent>,
+    session_id: Uuid,
+    opus_supported: bool,
+) {
+    let mut opus_encoder = if opus_supported {
+        match OpusFrameEncoder::new(TARGET_SAMPLE_RATE, 1) {
+            Ok(encoder) => Some(encoder),
+            Err(e) => {
+                warn!("Failed to create Opus encoder, falling back to raw PCM: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    loop {
+        tokio::select! {
+            chunk = audio_rx.recv() => {
+                let Some(chunk) = chunk else { break };
+
+                let result = if let Some(encoder) = opus_encoder.as_mut() {
+                    match encoder.push(&chunk.data) {
+                        Ok(frames) if frames.is_empty() => Ok(()),
+                        Ok(frames) => {
+                            let opus_chunk = shared::OpusChunk {
+                                session_id: chunk.session_id,
+                                frames,
+                                sample_rate: chunk.sample_rate,
+                                channels: chunk.channels,
+                                timestamp: chunk.timestamp,
+                            };
+                            protocol::send_message(&mut write_half, &ClientMessage::StreamAudioOpus(opus_chunk)).await
+                        }
+                        Err(e) => {
+                            warn!("Failed to Opus-encode audio chunk: {}", e);
+                            Ok(())
+                        }
+                    }
+                } else {
+                    protocol::send_message(&mut write_half, &ClientMessage::StreamAudio(chunk)).await
+                };
+
+                if let Err(e) = result {
+                    error!("Failed to stream audio chunk to daemon: {}", e);
+                    break;
+                }
+            }
+            event = endpoint_rx.recv() => {
+                match event {
+                    Some(EndpointEvent::SilenceTimeout) => {
+                        debug!("Silence timeout reached, auto-stopping recording");
+                        if let Err(e) = protocol::send_message(&mut write_half, &ClientMessage::StopRecording(session_id)).await {
+                            error!("Failed to send auto StopRecording: {}", e);
+                        }
+                        break;
+                    }
+                    Some(EndpointEvent::SpeechSegment(_)) => {}
+                    None => break,
+                }
+            }
+        }
+    }
+}
 
+async fn listen_for_transcription(mut read_half: OwnedReadHalf) -> Result<()> {
     // Listen for transcription updates from daemon
     loop {
         let timeout_duration = Duration::from_secs(60); // 60 second timeout
 
-        match timeout(timeout_duration, protocol::receive_message::<DaemonMessage>(stream)).await {
+        match timeout(timeout_duration, protocol::receive_message::<_, DaemonMessage>(&mut read_half)).await {
             Ok(Ok(response)) => {
                 match response {
                     DaemonMessage::TranscriptionUpdate { session_id: _, partial_text, is_final: _ } => {
@@ -146,4 +308,4 @@ async fn listen_for_transcription(stream: &mut UnixStream) -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
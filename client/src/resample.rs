@@ -0,0 +1,114 @@
+// Downmixes captured audio to mono and resamples it to the fixed rate
+// WhisperManager expects (16 kHz), using windowed-sinc interpolation so
+// devices running at 44.1/48 kHz don't produce garbled or wrong-speed
+// transcripts.
+
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+const HALF_TAPS: usize = 16; // 32-tap kernel total
+
+pub struct Resampler {
+    src_rate: u32,
+    channels: u16,
+    /// Tail of the downmixed input carried across chunk boundaries so the
+    /// sinc kernel has context right up to each cut. Prefixed with up to
+    /// HALF_TAPS already-consumed samples for left context - `carry_offset`
+    /// is where in `carry` the not-yet-resampled data actually starts.
+    carry: Vec<f32>,
+    carry_offset: f64,
+}
+
+impl Resampler {
+    pub fn new(src_rate: u32, channels: u16) -> Self {
+        Self {
+            src_rate,
+            channels,
+            carry: Vec::new(),
+            carry_offset: 0.0,
+        }
+    }
+
+    /// Downmix `data` (interleaved, `self.channels` channels) to mono and
+    /// resample it to `TARGET_SAMPLE_RATE`. Returns an empty vec if there
+    /// isn't yet enough input (plus carry-over) to produce a sample.
+    pub fn process(&mut self, data: &[f32]) -> Vec<f32> {
+        if self.src_rate == TARGET_SAMPLE_RATE && self.channels == 1 {
+            return data.to_vec();
+        }
+
+        let mono = downmix(data, self.channels);
+
+        let mut input = std::mem::take(&mut self.carry);
+        input.extend_from_slice(&mono);
+
+        if input.len() <= HALF_TAPS * 2 {
+            self.carry = input;
+            return Vec::new();
+        }
+
+        let ratio = self.src_rate as f64 / TARGET_SAMPLE_RATE as f64;
+        let usable = input.len() - HALF_TAPS; // leave lookahead for the kernel
+        let mut out = Vec::new();
+        let mut t = self.carry_offset;
+        while (t as usize) + HALF_TAPS < usable {
+            out.push(sinc_interpolate(&input, t, ratio));
+            t += ratio;
+        }
+
+        let consumed = (t as usize).min(input.len());
+        // Carry forward not just the unconsumed lookahead tail but also the
+        // last HALF_TAPS samples of the consumed region, so the kernel has
+        // real left-context (instead of sinc_interpolate's `idx < 0` treating
+        // it as zero) at the start of the next chunk. `carry_offset` tracks
+        // how far into that carried slice the not-yet-resampled data starts.
+        let carry_start = consumed.saturating_sub(HALF_TAPS);
+        self.carry_offset = t - carry_start as f64;
+        self.carry = input[carry_start..].to_vec();
+
+        out
+    }
+}
+
+fn downmix(data: &[f32], channels: u16) -> Vec<f32> {
+    if channels <= 1 {
+        return data.to_vec();
+    }
+    let channels = channels as usize;
+    data.chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+/// Blackman-windowed sinc interpolation at fractional position `t` (in input
+/// sample units), low-pass filtered at the Nyquist of the lower of the two
+/// rates to prevent aliasing when downsampling.
+fn sinc_interpolate(input: &[f32], t: f64, ratio: f64) -> f32 {
+    let cutoff = if ratio > 1.0 { 1.0 / ratio } else { 1.0 };
+    let center = t.floor() as i64;
+    let mut acc = 0.0f64;
+    for k in -(HALF_TAPS as i64)..(HALF_TAPS as i64) {
+        let idx = center + k;
+        if idx < 0 || idx as usize >= input.len() {
+            continue;
+        }
+        let x = t - idx as f64;
+        acc += input[idx as usize] as f64 * sinc(x * cutoff) * cutoff * blackman(x, HALF_TAPS as f64);
+    }
+    acc as f32
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        (std::f64::consts::PI * x).sin() / (std::f64::consts::PI * x)
+    }
+}
+
+fn blackman(x: f64, half_width: f64) -> f64 {
+    let n = (x + half_width) / (2.0 * half_width);
+    if !(0.0..=1.0).contains(&n) {
+        return 0.0;
+    }
+    0.42 - 0.5 * (2.0 * std::f64::consts::PI * n).cos() + 0.08 * (4.0 * std::f64::consts::PI * n).cos()
+}
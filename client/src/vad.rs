@@ -0,0 +1,118 @@
+// Frame-based voice-activity detector that runs directly inside the cpal
+// capture callback in `AudioCapture`, so it operates on whatever chunk size
+// the device hands back rather than a pre-buffered stream. Distinct from the
+// daemon's `VoiceActivityDetector`, which slices a live transcription buffer
+// server-side - this one only decides when the speaker has started and
+// stopped talking, so the client can auto-stop on silence.
+
+const FRAME_MS: u32 = 30;
+const NOISE_FLOOR_WINDOW_MS: u32 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VadEvent {
+    None,
+    SpeechStarted,
+    SpeechEnded,
+}
+
+pub struct ClientVad {
+    frame_samples: usize,
+    vad_threshold: f32,
+    hangover_frames: u32,
+    noise_floor_frames: u32,
+
+    pending: Vec<f32>,
+    noise_floor: f32,
+    frames_seen: u32,
+    in_speech: bool,
+    silence_frames: u32,
+}
+
+impl ClientVad {
+    /// `vad_threshold` is the minimum RMS energy above the calibrated noise
+    /// floor to count as speech. `hangover_ms` is how long energy must stay
+    /// below that threshold before a speech segment is considered ended
+    /// (keeps trailing consonants from being clipped).
+    pub fn new(sample_rate: u32, vad_threshold: f32, hangover_ms: u32) -> Self {
+        let frame_samples = ((sample_rate * FRAME_MS / 1000) as usize).max(1);
+        let hangover_frames = (hangover_ms / FRAME_MS).max(1);
+        let noise_floor_frames = (NOISE_FLOOR_WINDOW_MS / FRAME_MS).max(1);
+
+        Self {
+            frame_samples,
+            vad_threshold,
+            hangover_frames,
+            noise_floor_frames,
+            pending: Vec::new(),
+            noise_floor: 0.0,
+            frames_seen: 0,
+            in_speech: false,
+            silence_frames: 0,
+        }
+    }
+
+    /// Feed newly captured samples and return the most relevant event
+    /// produced by any whole frames they completed.
+    pub fn push(&mut self, data: &[f32]) -> VadEvent {
+        self.pending.extend_from_slice(data);
+        let mut event = VadEvent::None;
+
+        while self.pending.len() >= self.frame_samples {
+            let frame: Vec<f32> = self.pending.drain(..self.frame_samples).collect();
+            if let Some(e) = self.process_frame(&frame) {
+                event = e;
+            }
+        }
+
+        event
+    }
+
+    fn process_frame(&mut self, frame: &[f32]) -> Option<VadEvent> {
+        let energy = rms_energy(frame);
+        let zcr = zero_crossing_rate(frame);
+
+        // Calibrate the noise floor from the first ~300ms, assumed silence
+        // before the speaker starts talking.
+        if self.frames_seen < self.noise_floor_frames {
+            self.noise_floor =
+                (self.noise_floor * self.frames_seen as f32 + energy) / (self.frames_seen + 1) as f32;
+            self.frames_seen += 1;
+            return None;
+        }
+        self.frames_seen += 1;
+
+        // A high zero-crossing rate alongside low energy is typically hiss,
+        // not speech, so require a plausible voiced rate too.
+        let is_speech = energy > self.noise_floor + self.vad_threshold && zcr < 0.5;
+
+        if is_speech {
+            self.silence_frames = 0;
+            if !self.in_speech {
+                self.in_speech = true;
+                return Some(VadEvent::SpeechStarted);
+            }
+        } else if self.in_speech {
+            self.silence_frames += 1;
+            if self.silence_frames >= self.hangover_frames {
+                self.in_speech = false;
+                self.silence_frames = 0;
+                return Some(VadEvent::SpeechEnded);
+            }
+        }
+
+        None
+    }
+}
+
+fn rms_energy(frame: &[f32]) -> f32 {
+    let sum_sq: f32 = frame.iter().map(|s| s * s).sum();
+    (sum_sq / frame.len() as f32).sqrt()
+}
+
+fn zero_crossing_rate(frame: &[f32]) -> f32 {
+    if frame.len() < 2 {
+        return 0.0;
+    }
+    let crossings = frame.windows(2).filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0)).count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
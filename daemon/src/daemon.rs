@@ -1,53 +1,134 @@
 use anyhow::Result;
 use log::{info, error, debug};
-use shared::{Config, ClientMessage, DaemonMessage, DaemonStatus, TranscriptionSession, AudioChunk};
+use shared::{Config, ClientMessage, DaemonMessage, DaemonStatus, HistoryEntry, TranscriptionSession, AudioChunk, OpusChunk};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{SystemTime, Instant};
-use tokio::sync::RwLock;
+use std::time::{SystemTime, Instant, Duration};
+use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 
+use crate::denoise::SpectralDenoiser;
+use crate::history::HistoryStore;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use crate::opus_codec::OpusDecoderState;
+use crate::recording;
+use crate::spectrum::SpectrumAnalyzer;
+use crate::vad::{VadEvent, VoiceActivityDetector};
 use crate::whisper::WhisperManager;
 
+/// How long a finished session's raw PCM is kept around for a `SaveRecording`
+/// request to claim, for callers (like the CLI) that never send
+/// `ClearSession` - without this, `finished_audio` would otherwise grow by
+/// one full audio buffer per recording for the daemon's entire lifetime.
+const FINISHED_AUDIO_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// How far a streaming partial's re-transcription window reaches back into
+/// already-committed audio. Re-transcribing only the tail of a growing
+/// segment (instead of the whole thing) keeps `PartialReady` cheap as a
+/// session runs long, while enough overlap with the committed region lets
+/// `merge_partial` re-derive words that straddled the previous window edge.
+const STREAM_OVERLAP_MS: u64 = 300;
+
+/// A session's streaming/continuous partial transcription state: the text
+/// `merge_partial` has already committed from earlier sliding-window passes,
+/// so each new pass only needs to contribute the words past that point.
+#[derive(Default)]
+struct PartialState {
+    committed_text: String,
+}
+
+/// The raw PCM of a session that has finished recording, kept around just
+/// long enough for a `SaveRecording` request to claim it.
+struct FinishedAudio {
+    samples: Vec<f32>,
+    sample_rate: u32,
+    channels: u16,
+    finished_at: Instant,
+}
+
 pub struct AudioBuffer {
     data: Vec<f32>,
     sample_rate: u32,
     channels: u16,
     last_chunk_time: SystemTime,
+    vad: VoiceActivityDetector,
+    muted: bool,
+    spectrum: SpectrumAnalyzer,
+    denoiser: Option<SpectralDenoiser>,
+    speech_started: bool,
 }
 
 impl AudioBuffer {
-    fn new() -> Self {
+    fn new(vad_sensitivity: f32, energy_threshold: f32, silence_ms: u64, muted: bool, denoise_strength: Option<f32>) -> Self {
         Self {
             data: Vec::new(),
             sample_rate: 16000,
             channels: 1,
             last_chunk_time: SystemTime::now(),
+            vad: VoiceActivityDetector::new(vad_sensitivity, energy_threshold, silence_ms),
+            muted,
+            spectrum: SpectrumAnalyzer::new(),
+            denoiser: denoise_strength.map(SpectralDenoiser::new),
+            speech_started: false,
         }
     }
-    
+
     fn append_chunk(&mut self, chunk: &AudioChunk) {
-        self.data.extend_from_slice(&chunk.data);
-        self.sample_rate = chunk.sample_rate;
-        self.channels = chunk.channels;
-        self.last_chunk_time = chunk.timestamp;
+        self.append_pcm(&chunk.data, chunk.sample_rate, chunk.channels, chunk.timestamp);
     }
-    
+
+    fn append_pcm(&mut self, data: &[f32], sample_rate: u32, channels: u16, timestamp: SystemTime) {
+        self.data.extend_from_slice(data);
+        self.sample_rate = sample_rate;
+        self.channels = channels;
+        self.last_chunk_time = timestamp;
+    }
+
     fn duration_seconds(&self) -> f32 {
         if self.sample_rate == 0 || self.channels == 0 {
             return 0.0;
         }
         self.data.len() as f32 / (self.sample_rate * self.channels as u32) as f32
     }
-    
+
     fn is_silent_timeout(&self, timeout_seconds: f32) -> bool {
         self.last_chunk_time.elapsed().unwrap_or_default().as_secs_f32() > timeout_seconds
     }
-    
+
     fn get_all_audio(&self) -> Vec<f32> {
         // Return all audio data for final transcription
         self.data.clone()
     }
+
+    /// Drop samples up to `end` now that they've been finalized, and shift
+    /// the VAD's internal offsets back to match.
+    fn drain_up_to(&mut self, end: usize) {
+        let end = end.min(self.data.len());
+        self.data.drain(0..end);
+        self.vad.trim(end);
+    }
+
+    /// Before the first speech segment starts, any newly-appended audio is
+    /// presumed to be room noise, so fold it into the denoiser's per-bin
+    /// noise profile.
+    fn learn_noise_if_pre_speech(&mut self, new_data: &[f32]) {
+        if self.speech_started {
+            return;
+        }
+        if let Some(denoiser) = self.denoiser.as_mut() {
+            denoiser.learn_noise(new_data);
+        }
+    }
+
+    /// Spectrally gate `segment` if denoising is enabled for this session;
+    /// passes through unchanged otherwise.
+    fn denoise(&self, segment: &[f32]) -> Vec<f32> {
+        match &self.denoiser {
+            Some(denoiser) => denoiser.process(segment),
+            None => segment.to_vec(),
+        }
+    }
 }
 
 pub struct Daemon {
@@ -55,45 +136,225 @@ pub struct Daemon {
     whisper_manager: Arc<RwLock<WhisperManager>>,
     active_sessions: Arc<RwLock<HashMap<Uuid, TranscriptionSession>>>,
     audio_buffers: Arc<RwLock<HashMap<Uuid, AudioBuffer>>>,
+    opus_decoders: Arc<RwLock<HashMap<Uuid, OpusDecoderState>>>,
+    finished_audio: Arc<RwLock<HashMap<Uuid, FinishedAudio>>>,
+    partial_state: Arc<RwLock<HashMap<Uuid, PartialState>>>,
+    vad_sensitivity: Arc<RwLock<f32>>,
+    // Audio device name, broken out of `config` (which is otherwise fixed at
+    // startup) so `ReloadConfig` can hot-apply a newly-selected device
+    // without restarting the daemon.
+    audio_device: Arc<RwLock<Option<String>>>,
+    last_activity: Arc<RwLock<Instant>>,
+    model_events: broadcast::Sender<DaemonMessage>,
+    // Spectrum frames are pushed on every audio chunk - far more often than
+    // the transcription/lifecycle events on `model_events` - so they get
+    // their own, higher-capacity channel. Sharing the capacity-16
+    // `model_events` channel risked a burst of Spectrum frames pushing out
+    // an unconsumed TranscriptionUpdate and tripping RecvError::Lagged.
+    spectrum_events: broadcast::Sender<DaemonMessage>,
     start_time: Instant,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<Metrics>,
+    // None when `config.history.enabled` is false, so persisting a completed
+    // session is skipped entirely instead of opening a database nobody reads.
+    history: Option<Arc<HistoryStore>>,
 }
 
 impl Daemon {
     pub fn new(config: Config) -> Result<Self> {
+        let vad_sensitivity = config.whisper.vad_sensitivity;
+        let audio_device = config.audio.device.clone();
         let whisper_manager = Arc::new(RwLock::new(WhisperManager::new(&config.whisper)?));
-        
+        let active_sessions = Arc::new(RwLock::new(HashMap::new()));
+        let last_activity = Arc::new(RwLock::new(Instant::now()));
+        let (model_events, _) = broadcast::channel(16);
+        let (spectrum_events, _) = broadcast::channel(256);
+
+        #[cfg(feature = "metrics")]
+        let metrics = {
+            let metrics = Arc::new(Metrics::new());
+            metrics.clone().spawn_pusher(config.metrics.clone());
+            metrics
+        };
+
+        #[cfg(feature = "metrics")]
+        spawn_idle_unload_task(
+            whisper_manager.clone(),
+            active_sessions.clone(),
+            last_activity.clone(),
+            model_events.clone(),
+            metrics.clone(),
+        );
+        #[cfg(not(feature = "metrics"))]
+        spawn_idle_unload_task(
+            whisper_manager.clone(),
+            active_sessions.clone(),
+            last_activity.clone(),
+            model_events.clone(),
+        );
+
+        let finished_audio = Arc::new(RwLock::new(HashMap::new()));
+        spawn_finished_audio_eviction_task(finished_audio.clone());
+
+        let history = if config.history.enabled {
+            let db_path = Config::config_dir()?.join("history.db");
+            Some(Arc::new(HistoryStore::open(&db_path)?))
+        } else {
+            None
+        };
+
         Ok(Self {
             config,
             whisper_manager,
-            active_sessions: Arc::new(RwLock::new(HashMap::new())),
+            active_sessions,
             audio_buffers: Arc::new(RwLock::new(HashMap::new())),
+            opus_decoders: Arc::new(RwLock::new(HashMap::new())),
+            finished_audio,
+            partial_state: Arc::new(RwLock::new(HashMap::new())),
+            vad_sensitivity: Arc::new(RwLock::new(vad_sensitivity)),
+            audio_device: Arc::new(RwLock::new(audio_device)),
+            last_activity,
+            model_events,
+            spectrum_events,
             start_time: Instant::now(),
+            #[cfg(feature = "metrics")]
+            metrics,
+            history,
         })
     }
-    
+
+    /// Subscribe to out-of-band push events - model lifecycle
+    /// (`ModelLoading`/`ModelLoaded`/`ModelUnloaded`) and
+    /// `TranscriptionUpdate`/`TranscriptionComplete` for every
+    /// active session - so a client connection can forward them as they
+    /// happen without waiting for its own next request/response turn, and
+    /// so multiple connections (e.g. the egui app and the CLI client) can
+    /// observe the same session at once instead of racing over whichever
+    /// one happens to be the one sending that session's audio.
+    pub fn subscribe_model_events(&self) -> broadcast::Receiver<DaemonMessage> {
+        self.model_events.subscribe()
+    }
+
+    /// Subscribe to the dedicated `Spectrum` broadcast - separate from
+    /// `subscribe_model_events` because spectrum frames are pushed on every
+    /// audio chunk, far more often than transcription/lifecycle events.
+    pub fn subscribe_spectrum_events(&self) -> broadcast::Receiver<DaemonMessage> {
+        self.spectrum_events.subscribe()
+    }
+
+    async fn touch_activity(&self) {
+        *self.last_activity.write().await = Instant::now();
+    }
+
     pub async fn handle_message(&self, message: ClientMessage) -> DaemonMessage {
         debug!("Handling client message: {:?}", message);
-        
+
         match message {
             ClientMessage::StartRecording => {
                 self.start_recording().await
             }
-            ClientMessage::StopRecording => {
-                self.stop_recording().await
+            ClientMessage::StopRecording(session_id) => {
+                self.stop_recording(session_id).await
             }
             ClientMessage::StreamAudio(audio_chunk) => {
                 self.handle_audio_chunk(audio_chunk).await
             }
+            ClientMessage::StreamAudioOpus(opus_chunk) => {
+                self.handle_opus_chunk(opus_chunk).await
+            }
+            ClientMessage::ResumeSession(session_id) => {
+                self.resume_session(session_id).await
+            }
             ClientMessage::GetStatus => {
                 self.get_status().await
             }
+            ClientMessage::ClearSession(session_id) => {
+                self.clear_session(session_id).await
+            }
+            ClientMessage::SetSensitivity(sensitivity) => {
+                self.set_sensitivity(sensitivity).await
+            }
+            ClientMessage::SaveRecording { session_id, path } => {
+                self.save_recording(session_id, path).await
+            }
+            ClientMessage::SetMuted(session_id, muted) => {
+                self.set_muted(session_id, muted).await
+            }
+            ClientMessage::ReloadConfig => {
+                self.reload_config().await
+            }
+            ClientMessage::Subscribe(session_id) => {
+                self.subscribe_session(session_id).await
+            }
+            ClientMessage::ListHistory { limit, query } => {
+                self.list_history(limit, query).await
+            }
+            ClientMessage::GetSession(session_id) => {
+                self.get_history_session(session_id).await
+            }
+            ClientMessage::DeleteSession(session_id) => {
+                self.delete_history_session(session_id).await
+            }
+            ClientMessage::Retranscribe(session_id) => {
+                self.retranscribe(session_id).await
+            }
             ClientMessage::Shutdown => {
                 info!("Received shutdown command");
                 std::process::exit(0);
             }
         }
     }
-    
+
+    async fn set_sensitivity(&self, sensitivity: f32) -> DaemonMessage {
+        info!("Setting VAD sensitivity to {}", sensitivity);
+        *self.vad_sensitivity.write().await = sensitivity;
+
+        let mut buffers = self.audio_buffers.write().await;
+        for buffer in buffers.values_mut() {
+            buffer.vad.set_sensitivity(sensitivity);
+        }
+
+        self.get_status().await
+    }
+
+    /// Mute/unmute a single session without tearing it down: while muted,
+    /// incoming frames for that session are dropped before they reach the
+    /// buffer, so VAD never sees them and no transcription happens for that
+    /// stretch of audio. Scoped to `session_id` so muting one concurrent
+    /// recording doesn't silence every other session on the daemon.
+    async fn set_muted(&self, session_id: Uuid, muted: bool) -> DaemonMessage {
+        info!("Setting muted to {} for session {}", muted, session_id);
+
+        let mut buffers = self.audio_buffers.write().await;
+        match buffers.get_mut(&session_id) {
+            Some(buffer) => {
+                buffer.muted = muted;
+                DaemonMessage::Muted(muted)
+            }
+            None => DaemonMessage::Error("Session not found".to_string()),
+        }
+    }
+
+    /// Re-reads the config file and hot-applies the settings that support
+    /// it without a restart. Currently that's just `audio.device` - the
+    /// client picks this up next time it opens an `AudioCapture` for a new
+    /// recording, since there's no live cpal stream on the daemon side to
+    /// tear down. Everything else in `Config` (model path, IPC socket, etc.)
+    /// still requires a daemon restart to take effect.
+    async fn reload_config(&self) -> DaemonMessage {
+        info!("Reloading config from disk");
+        match Config::load() {
+            Ok(new_config) => {
+                *self.audio_device.write().await = new_config.audio.device;
+                DaemonMessage::ConfigReloaded
+            }
+            Err(e) => {
+                error!("Failed to reload config: {}", e);
+                DaemonMessage::Error(format!("Failed to reload config: {}", e))
+            }
+        }
+    }
+
     async fn start_recording(&self) -> DaemonMessage {
         info!("Starting new recording session");
         
@@ -106,11 +367,25 @@ impl Daemon {
             sessions.insert(session_id, session);
         }
         {
+            let sensitivity = *self.vad_sensitivity.read().await;
+            let denoise_strength = self.config.whisper.denoise.then_some(self.config.whisper.denoise_strength);
+            let energy_threshold = self.config.whisper.energy_threshold;
+            let silence_ms = self.config.whisper.silence_ms;
             let mut buffers = self.audio_buffers.write().await;
-            buffers.insert(session_id, AudioBuffer::new());
+            // Every new recording starts unmuted - mute is a per-session
+            // toggle (SetMuted targets a session_id), not a sticky default
+            // carried over from whatever another session was last set to.
+            buffers.insert(session_id, AudioBuffer::new(sensitivity, energy_threshold, silence_ms, false, denoise_strength));
         }
         
-        // Ensure whisper model is loaded
+        self.touch_activity().await;
+
+        // Ensure whisper model is loaded, announcing the load to anyone
+        // subscribed to model lifecycle events if it was actually unloaded.
+        let was_loaded = { self.whisper_manager.read().await.is_loaded() };
+        if !was_loaded {
+            let _ = self.model_events.send(DaemonMessage::ModelLoading);
+        }
         {
             let mut whisper = self.whisper_manager.write().await;
             if let Err(e) = whisper.ensure_loaded().await {
@@ -118,91 +393,692 @@ impl Daemon {
                 return DaemonMessage::Error(format!("Failed to load Whisper model: {}", e));
             }
         }
-        
+        if !was_loaded {
+            let _ = self.model_events.send(DaemonMessage::ModelLoaded);
+            #[cfg(feature = "metrics")]
+            self.metrics.record_model_loaded();
+        }
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record_recording_started();
+
         DaemonMessage::RecordingStarted(session_id)
     }
     
-    async fn stop_recording(&self) -> DaemonMessage {
-        info!("Stopping recording sessions");
-        
-        // Process any remaining audio in buffers before stopping
-        let final_transcriptions = {
+    async fn stop_recording(&self, session_id: Uuid) -> DaemonMessage {
+        info!("Stopping recording session {}", session_id);
+        self.touch_activity().await;
+
+        if !self.session_exists(session_id).await {
+            return DaemonMessage::Error("Session not found".to_string());
+        }
+
+        // Process any remaining audio in this session's buffer before stopping it.
+        let mut duration_secs = 0.0f32;
+        let final_transcription = {
             let mut buffers = self.audio_buffers.write().await;
             let mut sessions = self.active_sessions.write().await;
-            let mut results = Vec::new();
-            
-            for (session_id, buffer) in buffers.drain() {
+
+            let mut result = None;
+            if let Some(buffer) = buffers.remove(&session_id) {
                 if !buffer.data.is_empty() {
-                    info!("Processing final audio for session {}: {:.1}s of audio", 
-                        session_id, buffer.duration_seconds());
-                    
-                    // Transcribe the final audio
+                    duration_secs = buffer.duration_seconds();
+                    info!("Processing final audio for session {}: {:.1}s of audio",
+                        session_id, duration_secs);
+
                     let audio_data = buffer.get_all_audio();
+                    self.finished_audio.write().await.insert(session_id, FinishedAudio {
+                        samples: audio_data.clone(),
+                        sample_rate: buffer.sample_rate,
+                        channels: buffer.channels,
+                        finished_at: Instant::now(),
+                    });
+
+                    // Saved recordings keep the raw audio; only the copy fed
+                    // to Whisper gets spectrally gated.
+                    let transcription_input = buffer.denoise(&audio_data);
+
                     let mut whisper = self.whisper_manager.write().await;
-                    match whisper.transcribe_audio(&audio_data).await {
+                    let transcript = match whisper.transcribe_audio(&transcription_input).await {
                         Ok(transcription) => {
                             if let Some(session) = sessions.get_mut(&session_id) {
-                                session.text = transcription.clone();
+                                // Earlier streamed segments (if any) already
+                                // accumulated into session.text via
+                                // process_vad_event's SpeechEnded arm -
+                                // append this trailing chunk instead of
+                                // overwriting and discarding them.
+                                if !session.text.is_empty() && !transcription.trim().is_empty() {
+                                    session.text.push(' ');
+                                }
+                                session.text.push_str(&transcription);
                                 session.status = shared::SessionStatus::Completed;
-                                results.push(session.clone());
+                                result = Some(session.clone());
                             }
+                            Some(transcription)
                         }
                         Err(e) => {
                             error!("Failed to transcribe final audio for session {}: {}", session_id, e);
                             if let Some(session) = sessions.get_mut(&session_id) {
                                 session.status = shared::SessionStatus::Failed(e.to_string());
-                                results.push(session.clone());
+                                result = Some(session.clone());
                             }
+                            None
                         }
+                    };
+                    drop(whisper);
+
+                    if self.config.audio.save_recordings {
+                        self.archive_recording(session_id, &audio_data, buffer.sample_rate, buffer.channels, transcript.unwrap_or_default()).await;
+                    }
+                }
+            }
+
+            // No leftover buffered audio to transcribe here - e.g. the user
+            // stopped right after a pause - but earlier streamed segments may
+            // already have accumulated text into session.text. Report and
+            // archive that instead of silently dropping the whole streamed
+            // transcript.
+            if result.is_none() {
+                if let Some(session) = sessions.get_mut(&session_id) {
+                    if !session.text.is_empty() {
+                        session.status = shared::SessionStatus::Completed;
+                        result = Some(session.clone());
                     }
                 }
             }
-            
-            sessions.clear();
-            results
+
+            sessions.remove(&session_id);
+            result
         };
-        
-        // Return the final transcription if there's one session, otherwise just stopped
-        if let Some(session) = final_transcriptions.into_iter().next() {
-            DaemonMessage::TranscriptionComplete(session)
-        } else {
-            DaemonMessage::RecordingStopped
+
+        self.opus_decoders.write().await.remove(&session_id);
+        self.partial_state.write().await.remove(&session_id);
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record_recording_stopped();
+
+        match final_transcription {
+            Some(session) => {
+                #[cfg(feature = "metrics")]
+                if matches!(session.status, shared::SessionStatus::Completed) {
+                    let latency = SystemTime::now()
+                        .duration_since(session.created_at)
+                        .unwrap_or_default();
+                    self.metrics.record_transcription_complete(&session, latency);
+                }
+
+                if matches!(session.status, shared::SessionStatus::Completed) {
+                    self.record_history(&session, duration_secs);
+                }
+
+                let message = DaemonMessage::TranscriptionComplete(session);
+                let _ = self.model_events.send(message.clone());
+                message
+            }
+            None => DaemonMessage::RecordingStopped,
+        }
+    }
+
+    /// Re-attach to a session whose transport dropped and reconnected. The
+    /// session's `TranscriptionSession`/`AudioBuffer` are left untouched -
+    /// streaming just picks back up where it left off - this only confirms
+    /// the session is still alive on this side.
+    async fn resume_session(&self, session_id: Uuid) -> DaemonMessage {
+        info!("Resuming session {}", session_id);
+        self.touch_activity().await;
+
+        if !self.session_exists(session_id).await {
+            return DaemonMessage::Error("Session not found".to_string());
+        }
+
+        DaemonMessage::SessionResumed(session_id)
+    }
+
+    /// Confirms a session exists so a connection can start treating the
+    /// `TranscriptionUpdate`/`TranscriptionComplete` events it receives over
+    /// `subscribe_model_events` as belonging to a session it cares about, even
+    /// though it never sent that session's `StartRecording`/`StreamAudio` -
+    /// e.g. a status dashboard watching the same recording the egui app
+    /// started. The daemon doesn't track per-connection subscriptions itself:
+    /// every connection already receives every broadcast event, tagged with
+    /// its `session_id`, and filters client-side.
+    async fn subscribe_session(&self, session_id: Uuid) -> DaemonMessage {
+        if !self.session_exists(session_id).await {
+            return DaemonMessage::Error("Session not found".to_string());
+        }
+
+        DaemonMessage::Subscribed(session_id)
+    }
+
+    /// Blocking SQLite work runs on `spawn_blocking` rather than inline, the
+    /// same way `Metrics::spawn_pusher` keeps its Pushgateway push off the
+    /// async runtime's worker threads.
+    async fn list_history(&self, limit: usize, query: Option<String>) -> DaemonMessage {
+        let Some(store) = self.history.clone() else {
+            return DaemonMessage::HistoryList(Vec::new());
+        };
+
+        match tokio::task::spawn_blocking(move || store.list(limit, query.as_deref())).await {
+            Ok(Ok(entries)) => DaemonMessage::HistoryList(entries),
+            Ok(Err(e)) => {
+                error!("Failed to list history: {}", e);
+                DaemonMessage::Error(format!("Failed to list history: {}", e))
+            }
+            Err(e) => {
+                error!("History list task panicked: {}", e);
+                DaemonMessage::Error("Failed to list history".to_string())
+            }
+        }
+    }
+
+    async fn get_history_session(&self, session_id: Uuid) -> DaemonMessage {
+        let Some(store) = self.history.clone() else {
+            return DaemonMessage::HistorySession(None);
+        };
+
+        match tokio::task::spawn_blocking(move || store.get(session_id)).await {
+            Ok(Ok(entry)) => DaemonMessage::HistorySession(entry),
+            Ok(Err(e)) => {
+                error!("Failed to fetch history entry {}: {}", session_id, e);
+                DaemonMessage::Error(format!("Failed to fetch history entry: {}", e))
+            }
+            Err(e) => {
+                error!("History fetch task panicked: {}", e);
+                DaemonMessage::Error("Failed to fetch history entry".to_string())
+            }
+        }
+    }
+
+    async fn delete_history_session(&self, session_id: Uuid) -> DaemonMessage {
+        let Some(store) = self.history.clone() else {
+            return DaemonMessage::HistoryDeleted(session_id);
+        };
+
+        match tokio::task::spawn_blocking(move || store.delete(session_id)).await {
+            Ok(Ok(_)) => DaemonMessage::HistoryDeleted(session_id),
+            Ok(Err(e)) => {
+                error!("Failed to delete history entry {}: {}", session_id, e);
+                DaemonMessage::Error(format!("Failed to delete history entry: {}", e))
+            }
+            Err(e) => {
+                error!("History delete task panicked: {}", e);
+                DaemonMessage::Error("Failed to delete history entry".to_string())
+            }
+        }
+    }
+
+    /// Write a finished session's raw PCM to disk as WAV. Refuses (and
+    /// leaves nothing on disk) if the capture was empty or pure silence, so
+    /// accidental start/stop presses don't leave garbage files behind.
+    async fn save_recording(&self, session_id: Uuid, path: std::path::PathBuf) -> DaemonMessage {
+        info!("Saving recording for session {} to {:?}", session_id, path);
+
+        let audio = self.finished_audio.read().await;
+        let Some(audio) = audio.get(&session_id) else {
+            return DaemonMessage::Error("No finished recording for that session".to_string());
+        };
+
+        if recording::is_effectively_empty(&audio.samples) {
+            return DaemonMessage::Error("recording was empty".to_string());
+        }
+
+        match recording::save_wav(&path, &audio.samples, audio.sample_rate, audio.channels) {
+            Ok(()) => {
+                let duration_secs = audio.samples.len() as f32
+                    / (audio.sample_rate * audio.channels as u32) as f32;
+                DaemonMessage::RecordingSaved { path, duration_secs }
+            }
+            Err(e) => {
+                error!("Failed to save recording for session {}: {}", session_id, e);
+                DaemonMessage::Error(format!("Failed to save recording: {}", e))
+            }
+        }
+    }
+
+    /// Writes a finished session's raw audio and transcript to
+    /// `config.audio.recordings_dir` as `<session_id>.wav` + `.yaml`, so it
+    /// can be replayed or re-transcribed later via
+    /// `WhisperManager::retranscribe`. Errors are logged, not propagated -
+    /// archival is best-effort and shouldn't fail the transcription the
+    /// caller is waiting on.
+    async fn archive_recording(
+        &self,
+        session_id: Uuid,
+        samples: &[f32],
+        sample_rate: u32,
+        channels: u16,
+        transcript: String,
+    ) {
+        let dir = &self.config.audio.recordings_dir;
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            error!("Failed to create recordings directory {:?}: {}", dir, e);
+            return;
+        }
+
+        let wav_path = dir.join(format!("{}.wav", session_id));
+        if let Err(e) = recording::save_wav_pcm16(&wav_path, samples, sample_rate, channels) {
+            error!("Failed to archive recording for session {}: {}", session_id, e);
+            return;
+        }
+
+        let sidecar = recording::RecordingSidecar {
+            transcript,
+            timestamp: SystemTime::now(),
+            model_path: self.config.whisper.model_path.clone(),
+            language: self.config.whisper.language.clone(),
+        };
+        let sidecar_path = dir.join(format!("{}.yaml", session_id));
+        if let Err(e) = recording::save_sidecar(&sidecar_path, &sidecar) {
+            error!("Failed to write sidecar for session {}: {}", session_id, e);
+        }
+    }
+
+    /// Re-runs transcription over the WAV `archive_recording` wrote for
+    /// `session_id`, against whatever model is currently loaded - so
+    /// switching to a bigger/better model and re-processing an old recording
+    /// doesn't require re-speaking it. Only archived (`save_recordings`)
+    /// sessions have a WAV to retranscribe.
+    async fn retranscribe(&self, session_id: Uuid) -> DaemonMessage {
+        let wav_path = self.config.audio.recordings_dir.join(format!("{}.wav", session_id));
+        if !wav_path.exists() {
+            return DaemonMessage::Error(format!("No archived recording for session {}", session_id));
+        }
+
+        let mut whisper = self.whisper_manager.write().await;
+        if let Err(e) = whisper.ensure_loaded().await {
+            error!("Failed to load Whisper model for retranscribe: {}", e);
+            return DaemonMessage::Error(format!("Failed to load Whisper model: {}", e));
+        }
+
+        match whisper.retranscribe(&wav_path).await {
+            Ok(text) => {
+                self.touch_activity().await;
+                DaemonMessage::Retranscribed { session_id, text }
+            }
+            Err(e) => {
+                error!("Retranscribe failed for session {}: {}", session_id, e);
+                DaemonMessage::Error(format!("Retranscribe failed: {}", e))
+            }
+        }
+    }
+
+    /// Fire-and-forget persist of a completed session to the history
+    /// database, off the async runtime via `spawn_blocking` like every other
+    /// `HistoryStore` call. Does nothing if `config.history.enabled` is
+    /// false.
+    fn record_history(&self, session: &TranscriptionSession, duration_secs: f32) {
+        let Some(store) = self.history.clone() else {
+            return;
+        };
+
+        let entry = HistoryEntry {
+            id: session.id,
+            text: session.text.clone(),
+            confidence: session.confidence,
+            created_at: session.created_at,
+            duration_secs,
+            model_name: self
+                .config
+                .whisper
+                .model_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+        };
+        let config = self.config.history.clone();
+
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = store.record(&entry, &config) {
+                error!("Failed to record history entry {}: {}", entry.id, e);
+            }
+        });
+    }
+
+    async fn clear_session(&self, session_id: Uuid) -> DaemonMessage {
+        info!("Clearing session {}", session_id);
+
+        if !self.session_exists(session_id).await {
+            return DaemonMessage::Error("Session not found".to_string());
+        }
+
+        self.audio_buffers.write().await.remove(&session_id);
+        self.opus_decoders.write().await.remove(&session_id);
+        self.finished_audio.write().await.remove(&session_id);
+        self.partial_state.write().await.remove(&session_id);
+        if let Some(session) = self.active_sessions.write().await.get_mut(&session_id) {
+            session.text.clear();
+            session.status = shared::SessionStatus::Recording;
         }
+
+        DaemonMessage::SessionCleared
     }
     
     async fn handle_audio_chunk(&self, audio_chunk: AudioChunk) -> DaemonMessage {
-        debug!("Received audio chunk for session {} with {} samples", 
+        debug!("Received audio chunk for session {} with {} samples",
             audio_chunk.session_id, audio_chunk.data.len());
-        
-        // Check if session exists
-        let session_exists = {
-            let sessions = self.active_sessions.read().await;
-            sessions.contains_key(&audio_chunk.session_id)
-        };
-        
-        if !session_exists {
+
+        let session_id = audio_chunk.session_id;
+        if !self.session_exists(session_id).await {
             return DaemonMessage::Error("Session not found".to_string());
         }
-        
-        // Just add audio to buffer - no transcription during streaming
-        {
+
+        let vad_event = {
             let mut buffers = self.audio_buffers.write().await;
-            if let Some(buffer) = buffers.get_mut(&audio_chunk.session_id) {
+            if let Some(buffer) = buffers.get_mut(&session_id) {
+                if buffer.muted {
+                    debug!("Dropping audio chunk for muted session {}", session_id);
+                    return DaemonMessage::TranscriptionUpdate {
+                        session_id,
+                        partial_text: String::new(),
+                        is_final: false,
+                    };
+                }
                 buffer.append_chunk(&audio_chunk);
+                buffer.learn_noise_if_pre_speech(&audio_chunk.data);
                 debug!("Buffer now has {:.1}s of audio", buffer.duration_seconds());
+                let bands = buffer.spectrum.analyze(&buffer.data);
+                let _ = self.spectrum_events.send(DaemonMessage::Spectrum { session_id, bands });
+                let event = buffer.vad.process(&buffer.data);
+                if matches!(event, VadEvent::SpeechStarted) {
+                    buffer.speech_started = true;
+                }
+                event
             } else {
-                error!("Audio buffer not found for session {}", audio_chunk.session_id);
+                error!("Audio buffer not found for session {}", session_id);
                 return DaemonMessage::Error("Audio buffer not found".to_string());
             }
+        };
+
+        self.process_vad_event(session_id, vad_event).await
+    }
+
+    async fn handle_opus_chunk(&self, opus_chunk: OpusChunk) -> DaemonMessage {
+        debug!("Received Opus chunk for session {} with {} frames",
+            opus_chunk.session_id, opus_chunk.frames.len());
+
+        let session_id = opus_chunk.session_id;
+        if !self.session_exists(session_id).await {
+            return DaemonMessage::Error("Session not found".to_string());
         }
-        
-        // Just acknowledge receipt
-        DaemonMessage::TranscriptionUpdate {
-            session_id: audio_chunk.session_id,
-            partial_text: "".to_string(),
+
+        let pcm = {
+            let mut decoders = self.opus_decoders.write().await;
+            if !decoders.contains_key(&session_id) {
+                let decoder = match OpusDecoderState::new(opus_chunk.sample_rate, opus_chunk.channels) {
+                    Ok(decoder) => decoder,
+                    Err(e) => {
+                        error!("Failed to create Opus decoder for session {}: {}", session_id, e);
+                        return DaemonMessage::Error(format!("Failed to create Opus decoder: {}", e));
+                    }
+                };
+                decoders.insert(session_id, decoder);
+            }
+
+            let decoder = decoders.get_mut(&session_id).expect("decoder was just inserted");
+            match decoder.decode_frames(&opus_chunk.frames) {
+                Ok(pcm) => pcm,
+                Err(e) => {
+                    error!("Failed to decode Opus chunk for session {}: {}", session_id, e);
+                    return DaemonMessage::Error(format!("Failed to decode Opus audio: {}", e));
+                }
+            }
+        };
+
+        let vad_event = {
+            let mut buffers = self.audio_buffers.write().await;
+            if let Some(buffer) = buffers.get_mut(&session_id) {
+                if buffer.muted {
+                    debug!("Dropping Opus chunk for muted session {}", session_id);
+                    return DaemonMessage::TranscriptionUpdate {
+                        session_id,
+                        partial_text: String::new(),
+                        is_final: false,
+                    };
+                }
+                buffer.append_pcm(&pcm, opus_chunk.sample_rate, opus_chunk.channels, opus_chunk.timestamp);
+                buffer.learn_noise_if_pre_speech(&pcm);
+                debug!("Buffer now has {:.1}s of audio", buffer.duration_seconds());
+                let bands = buffer.spectrum.analyze(&buffer.data);
+                let _ = self.spectrum_events.send(DaemonMessage::Spectrum { session_id, bands });
+                let event = buffer.vad.process(&buffer.data);
+                if matches!(event, VadEvent::SpeechStarted) {
+                    buffer.speech_started = true;
+                }
+                event
+            } else {
+                error!("Audio buffer not found for session {}", session_id);
+                return DaemonMessage::Error("Audio buffer not found".to_string());
+            }
+        };
+
+        self.process_vad_event(session_id, vad_event).await
+    }
+
+    async fn session_exists(&self, session_id: Uuid) -> bool {
+        let sessions = self.active_sessions.read().await;
+        sessions.contains_key(&session_id)
+    }
+
+    // Outside continuous mode (which finalizes through
+    // `finalize_continuous_segment` instead), a SpeechEnded event broadcasts
+    // both a VoiceActivityEnded and the final
+    // TranscriptionUpdate via `model_events`, so every subscribed connection
+    // sees both - not just whichever connection happened to send the
+    // triggering audio chunk.
+    //
+    // When both `config.whisper.streaming` and `config.whisper.continuous`
+    // are off, segment boundaries are only
+    // used to report `VoiceActivityDetected` - audio keeps accumulating
+    // untouched in the buffer so `stop_recording` can run its one-shot
+    // transcription over the whole recording, matching pre-streaming
+    // behavior.
+    async fn process_vad_event(&self, session_id: Uuid, vad_event: VadEvent) -> DaemonMessage {
+        if !self.config.whisper.streaming && !self.config.whisper.continuous {
+            return match vad_event {
+                VadEvent::SpeechStarted => DaemonMessage::VoiceActivityDetected,
+                _ => DaemonMessage::TranscriptionUpdate {
+                    session_id,
+                    partial_text: String::new(),
+                    is_final: false,
+                },
+            };
+        }
+
+        if self.config.whisper.continuous {
+            if let VadEvent::SpeechEnded { start, end } = vad_event {
+                return self.finalize_continuous_segment(session_id, start, end).await;
+            }
+        }
+
+        match vad_event {
+            VadEvent::SpeechStarted => DaemonMessage::VoiceActivityDetected,
+            VadEvent::PartialReady { start, end } => {
+                let segment = {
+                    let buffers = self.audio_buffers.read().await;
+                    let buffer = &buffers[&session_id];
+                    let window_start = stream_window_start(buffer.sample_rate, start, end);
+                    buffer.denoise(&buffer.data[window_start..end])
+                };
+
+                let mut whisper = self.whisper_manager.write().await;
+                let result = whisper.transcribe_audio(&segment).await;
+                drop(whisper);
+                self.touch_activity().await;
+                match result {
+                    Ok(window_text) => {
+                        let partial_text = self.merge_partial(session_id, &window_text).await;
+                        let message = DaemonMessage::TranscriptionUpdate {
+                            session_id,
+                            partial_text,
+                            is_final: false,
+                        };
+                        let _ = self.model_events.send(message.clone());
+                        message
+                    }
+                    Err(e) => {
+                        error!("Partial transcription failed for session {}: {}", session_id, e);
+                        DaemonMessage::Error(format!("Partial transcription failed: {}", e))
+                    }
+                }
+            }
+            VadEvent::SpeechEnded { start, end } => {
+                let segment = {
+                    let buffers = self.audio_buffers.read().await;
+                    let buffer = &buffers[&session_id];
+                    let window_start = stream_window_start(buffer.sample_rate, start, end);
+                    buffer.denoise(&buffer.data[window_start..end])
+                };
+
+                let mut whisper = self.whisper_manager.write().await;
+                let result = whisper.transcribe_audio(&segment).await;
+                drop(whisper);
+                self.touch_activity().await;
+
+                // Drop the finalized region now that we've transcribed it.
+                {
+                    let mut buffers = self.audio_buffers.write().await;
+                    if let Some(buffer) = buffers.get_mut(&session_id) {
+                        buffer.drain_up_to(end);
+                    }
+                }
+
+                match result {
+                    Ok(window_text) => {
+                        let final_text = self.merge_partial(session_id, &window_text).await;
+                        self.partial_state.write().await.remove(&session_id);
+                        // Persist this segment's text into the session now,
+                        // so stop_recording still has something to report
+                        // and archive even if the user stops right after a
+                        // pause and the trailing buffer is empty.
+                        if let Some(session) = self.active_sessions.write().await.get_mut(&session_id) {
+                            if !session.text.is_empty() && !final_text.trim().is_empty() {
+                                session.text.push(' ');
+                            }
+                            session.text.push_str(&final_text);
+                        }
+                        let _ = self.model_events.send(DaemonMessage::VoiceActivityEnded);
+                        let message = DaemonMessage::TranscriptionUpdate {
+                            session_id,
+                            partial_text: final_text,
+                            is_final: true,
+                        };
+                        let _ = self.model_events.send(message.clone());
+                        message
+                    }
+                    Err(e) => {
+                        error!("Final segment transcription failed for session {}: {}", session_id, e);
+                        DaemonMessage::Error(format!("Segment transcription failed: {}", e))
+                    }
+                }
+            }
+            VadEvent::None => DaemonMessage::TranscriptionUpdate {
+                session_id,
+                partial_text: String::new(),
+                is_final: false,
+            },
         }
     }
-    
+
+    /// Reconciles a freshly-transcribed sliding window against this
+    /// session's previously committed text. The window overlaps the tail end
+    /// of what's already committed (see `STREAM_OVERLAP_MS`/`stream_window_start`),
+    /// so its leading words are expected to repeat the committed text's own
+    /// tail; the longest run of `window_text`'s leading words that matches
+    /// `committed_text`'s trailing words (word-boundary longest-common
+    /// prefix/suffix) is treated as the overlap and dropped, and whatever's
+    /// left is appended as newly-stable text. Returns the updated committed
+    /// text, which becomes this partial's `TranscriptionUpdate::partial_text`.
+    async fn merge_partial(&self, session_id: Uuid, window_text: &str) -> String {
+        let mut states = self.partial_state.write().await;
+        let state = states.entry(session_id).or_default();
+
+        let committed_words: Vec<&str> = state.committed_text.split_whitespace().collect();
+        let window_words: Vec<&str> = window_text.split_whitespace().collect();
+
+        let max_overlap = committed_words.len().min(window_words.len());
+        let overlap = (1..=max_overlap)
+            .rev()
+            .find(|&candidate| committed_words[committed_words.len() - candidate..] == window_words[..candidate])
+            .unwrap_or(0);
+
+        let new_words = &window_words[overlap..];
+        if !new_words.is_empty() {
+            if !state.committed_text.is_empty() {
+                state.committed_text.push(' ');
+            }
+            state.committed_text.push_str(&new_words.join(" "));
+        }
+
+        state.committed_text.clone()
+    }
+
+    /// Continuous mode's segment finalization: reports the utterance as its
+    /// own `TranscriptionComplete` so the popup can accumulate it into a
+    /// growing transcript, but unlike `stop_recording` leaves the session and
+    /// audio buffer open afterward so the next utterance begins without an
+    /// explicit `StopRecording` - screenpipe's always-on segmentation,
+    /// applied one utterance at a time instead of a continuous capture file.
+    async fn finalize_continuous_segment(&self, session_id: Uuid, start: usize, end: usize) -> DaemonMessage {
+        let (segment, sample_rate) = {
+            let buffers = self.audio_buffers.read().await;
+            let buffer = &buffers[&session_id];
+            (buffer.denoise(&buffer.data[start..end]), buffer.sample_rate.max(1))
+        };
+        let duration_secs = (end - start) as f32 / sample_rate as f32;
+
+        let mut whisper = self.whisper_manager.write().await;
+        let result = whisper.transcribe_audio(&segment).await;
+        drop(whisper);
+        self.touch_activity().await;
+
+        {
+            let mut buffers = self.audio_buffers.write().await;
+            if let Some(buffer) = buffers.get_mut(&session_id) {
+                buffer.drain_up_to(end);
+            }
+        }
+        // Each continuous utterance restarts PartialReady's sliding window
+        // from scratch, so nothing from this segment's committed text should
+        // bleed into the next one's merge_partial calls.
+        self.partial_state.write().await.remove(&session_id);
+
+        let text = match result {
+            Ok(text) => text,
+            Err(e) => {
+                error!("Continuous segment transcription failed for session {}: {}", session_id, e);
+                return DaemonMessage::Error(format!("Segment transcription failed: {}", e));
+            }
+        };
+
+        let completed = {
+            let mut sessions = self.active_sessions.write().await;
+            let Some(session) = sessions.get_mut(&session_id) else {
+                return DaemonMessage::Error("Session not found".to_string());
+            };
+            session.text = text;
+            session.status = shared::SessionStatus::Completed;
+            let completed = session.clone();
+            // The session stays open for the next utterance in continuous mode.
+            session.status = shared::SessionStatus::Recording;
+            completed
+        };
+
+        #[cfg(feature = "metrics")]
+        {
+            let latency = SystemTime::now()
+                .duration_since(completed.created_at)
+                .unwrap_or_default();
+            self.metrics.record_transcription_complete(&completed, latency);
+        }
+
+        self.record_history(&completed, duration_secs);
+
+        let message = DaemonMessage::TranscriptionComplete(completed);
+        let _ = self.model_events.send(message.clone());
+        message
+    }
+
     async fn get_status(&self) -> DaemonMessage {
         let whisper_loaded = {
             let whisper = self.whisper_manager.read().await;
@@ -213,13 +1089,108 @@ impl Daemon {
             let sessions = self.active_sessions.read().await;
             sessions.keys().copied().collect()
         };
-        
+
         let status = DaemonStatus {
             model_loaded: whisper_loaded,
             active_sessions: active_session_ids,
             uptime: self.start_time.elapsed(),
+            audio_device: self.audio_device.read().await.clone().unwrap_or_else(|| "default".to_string()),
+            buffer_size: self.config.audio.buffer_size,
+            vad_sensitivity: *self.vad_sensitivity.read().await,
+            opus_supported: true,
         };
-        
+
         DaemonMessage::Status(status)
     }
+}
+
+/// Start of the audio slice a `PartialReady`/`SpeechEnded` pass should
+/// re-transcribe: the tail `STREAM_OVERLAP_MS` of the segment-so-far,
+/// clamped to the segment's own start so short segments transcribe from
+/// their true beginning instead of underflowing.
+fn stream_window_start(sample_rate: u32, segment_start: usize, end: usize) -> usize {
+    let overlap_samples = (STREAM_OVERLAP_MS as usize * sample_rate.max(1) as usize) / 1000;
+    end.saturating_sub(overlap_samples).max(segment_start)
+}
+
+/// Periodically drops any `finished_audio` entry older than
+/// `FINISHED_AUDIO_TTL`, so a caller that never sends `SaveRecording` or
+/// `ClearSession` for a finished session (the CLI has no flag for either)
+/// doesn't leak that session's raw PCM for the daemon's entire lifetime.
+fn spawn_finished_audio_eviction_task(finished_audio: Arc<RwLock<HashMap<Uuid, FinishedAudio>>>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            finished_audio.write().await.retain(|_, audio| audio.finished_at.elapsed() < FINISHED_AUDIO_TTL);
+        }
+    });
+}
+
+/// Periodically checks whether the Whisper model has sat idle - loaded, but
+/// with no active sessions - for longer than its configured timeout, and
+/// unloads it to release the weights (and any accelerator memory) until the
+/// next recording needs them again.
+#[cfg(feature = "metrics")]
+fn spawn_idle_unload_task(
+    whisper_manager: Arc<RwLock<WhisperManager>>,
+    active_sessions: Arc<RwLock<HashMap<Uuid, TranscriptionSession>>>,
+    last_activity: Arc<RwLock<Instant>>,
+    model_events: broadcast::Sender<DaemonMessage>,
+    metrics: Arc<Metrics>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(5));
+        loop {
+            interval.tick().await;
+
+            let mut whisper = whisper_manager.write().await;
+            if !whisper.is_loaded() {
+                continue;
+            }
+
+            let has_active_sessions = !active_sessions.read().await.is_empty();
+            if has_active_sessions {
+                continue;
+            }
+
+            let idle_for = last_activity.read().await.elapsed();
+            if idle_for > whisper.idle_timeout() {
+                whisper.unload();
+                let _ = model_events.send(DaemonMessage::ModelUnloaded);
+                metrics.record_model_unloaded();
+            }
+        }
+    });
+}
+
+#[cfg(not(feature = "metrics"))]
+fn spawn_idle_unload_task(
+    whisper_manager: Arc<RwLock<WhisperManager>>,
+    active_sessions: Arc<RwLock<HashMap<Uuid, TranscriptionSession>>>,
+    last_activity: Arc<RwLock<Instant>>,
+    model_events: broadcast::Sender<DaemonMessage>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(5));
+        loop {
+            interval.tick().await;
+
+            let mut whisper = whisper_manager.write().await;
+            if !whisper.is_loaded() {
+                continue;
+            }
+
+            let has_active_sessions = !active_sessions.read().await.is_empty();
+            if has_active_sessions {
+                continue;
+            }
+
+            let idle_for = last_activity.read().await.elapsed();
+            if idle_for > whisper.idle_timeout() {
+                whisper.unload();
+                let _ = model_events.send(DaemonMessage::ModelUnloaded);
+            }
+        }
+    });
 }
\ No newline at end of file
@@ -0,0 +1,130 @@
+// Spectral noise-gate denoising applied to a segment before it reaches
+// `WhisperManager::transcribe_audio`, to raise accuracy for noisy-room
+// recordings. Runs a short-time Fourier transform over overlapping Hann
+// windows, attenuates bins that sit close to a learned noise floor, and
+// reconstructs via overlap-add. This is a preprocessing stage only - it
+// never feeds back into VAD or the spectrum meter.
+
+use num_complex::Complex32;
+use realfft::RealFftPlanner;
+
+const WINDOW_SIZE: usize = 512;
+const HOP_SIZE: usize = WINDOW_SIZE / 2; // 50% overlap
+const SMOOTHING_FLOOR: f32 = 0.1; // minimum gain applied to a gated bin, avoids musical-noise artifacts
+
+pub struct SpectralDenoiser {
+    gate_factor: f32,
+    noise_profile: Vec<f32>,
+    profile_frames: u32,
+}
+
+impl SpectralDenoiser {
+    /// `strength` is 0.0 (mildest: only bins below the learned noise floor
+    /// itself are gated) to 1.0 (aggressive: bins up to 4x the floor are
+    /// gated) - how far above the learned noise floor a bin's magnitude must
+    /// sit to pass through ungated.
+    pub fn new(strength: f32) -> Self {
+        Self {
+            gate_factor: 1.0 + strength.clamp(0.0, 1.0) * 3.0,
+            noise_profile: vec![0.0; WINDOW_SIZE / 2 + 1],
+            profile_frames: 0,
+        }
+    }
+
+    /// Fold audio known to be non-speech (leading silence before the first
+    /// VAD speech segment) into the running per-bin noise magnitude profile.
+    pub fn learn_noise(&mut self, data: &[f32]) {
+        let mut pos = 0;
+        while pos + WINDOW_SIZE <= data.len() {
+            let mags = frame_magnitudes(&data[pos..pos + WINDOW_SIZE]);
+            let weight = 1.0 / (self.profile_frames + 1) as f32;
+            for (bin, mag) in self.noise_profile.iter_mut().zip(mags.iter()) {
+                *bin = *bin * (1.0 - weight) + mag * weight;
+            }
+            self.profile_frames += 1;
+            pos += HOP_SIZE;
+        }
+    }
+
+    /// Spectrally gate `data` via overlap-add STFT, attenuating bins whose
+    /// magnitude sits below `noise_profile[bin] * gate_factor` toward zero
+    /// rather than hard-muting them.
+    pub fn process(&self, data: &[f32]) -> Vec<f32> {
+        if self.profile_frames == 0 || data.len() < WINDOW_SIZE {
+            return data.to_vec();
+        }
+
+        let window = hann_window();
+        let mut out = vec![0.0f32; data.len()];
+        let mut norm = vec![0.0f32; data.len()];
+
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(WINDOW_SIZE);
+        let ifft = planner.plan_fft_inverse(WINDOW_SIZE);
+
+        let mut pos = 0;
+        while pos + WINDOW_SIZE <= data.len() {
+            let mut windowed: Vec<f32> = data[pos..pos + WINDOW_SIZE]
+                .iter()
+                .zip(window.iter())
+                .map(|(s, w)| s * w)
+                .collect();
+
+            let mut spectrum = fft.make_output_vec();
+            fft.process(&mut windowed, &mut spectrum).expect("fft size mismatch");
+
+            for (bin, value) in spectrum.iter_mut().enumerate() {
+                let mag = value.norm();
+                let threshold = self.noise_profile[bin] * self.gate_factor;
+                if threshold > 1e-8 && mag < threshold {
+                    let gain = SMOOTHING_FLOOR.max(mag / threshold);
+                    *value *= gain;
+                }
+            }
+
+            let mut reconstructed = ifft.make_output_vec();
+            ifft.process(&mut spectrum, &mut reconstructed).expect("ifft size mismatch");
+
+            // realfft's inverse doesn't normalize by window size, and
+            // overlap-add needs the synthesis window re-applied for
+            // reconstruction at 50% overlap to stay artifact-free.
+            let scale = 1.0 / WINDOW_SIZE as f32;
+            for (i, sample) in reconstructed.iter().enumerate() {
+                out[pos + i] += sample * scale * window[i];
+                norm[pos + i] += window[i] * window[i];
+            }
+
+            pos += HOP_SIZE;
+        }
+
+        // `pos + WINDOW_SIZE <= data.len()` above means up to one HOP_SIZE of
+        // trailing samples never falls inside any window and is left with a
+        // zero `norm` weight - pass those straight through from the original
+        // signal instead of leaving them at silence.
+        for (i, (sample, weight)) in out.iter_mut().zip(norm.iter()).enumerate() {
+            if *weight > 1e-6 {
+                *sample /= weight;
+            } else {
+                *sample = data[i];
+            }
+        }
+
+        out
+    }
+}
+
+fn frame_magnitudes(frame: &[f32]) -> Vec<f32> {
+    let window = hann_window();
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(WINDOW_SIZE);
+    let mut windowed: Vec<f32> = frame.iter().zip(window.iter()).map(|(s, w)| s * w).collect();
+    let mut spectrum = fft.make_output_vec();
+    fft.process(&mut windowed, &mut spectrum).expect("fft size mismatch");
+    spectrum.iter().map(Complex32::norm).collect()
+}
+
+fn hann_window() -> Vec<f32> {
+    (0..WINDOW_SIZE)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (WINDOW_SIZE - 1) as f32).cos())
+        .collect()
+}
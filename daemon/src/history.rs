@@ -0,0 +1,157 @@
+// Persists completed transcriptions to a local SQLite database so they can be
+// recalled, searched, and re-copied after the session that produced them is
+// gone - screenpipe's always-persist-everything approach, applied to
+// individual utterances rather than a continuous capture stream. An FTS5
+// virtual table kept in sync via triggers backs the full-text search.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use shared::{HistoryConfig, HistoryEntry};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use uuid::Uuid;
+
+pub struct HistoryStore {
+    conn: Mutex<Connection>,
+}
+
+impl HistoryStore {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open history database at {:?}", path))?;
+
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS history (
+                id TEXT PRIMARY KEY,
+                text TEXT NOT NULL,
+                confidence REAL,
+                created_at INTEGER NOT NULL,
+                duration_secs REAL NOT NULL,
+                model_name TEXT NOT NULL
+            );
+            CREATE VIRTUAL TABLE IF NOT EXISTS history_fts USING fts5(
+                text, content='history', content_rowid='rowid'
+            );
+            CREATE TRIGGER IF NOT EXISTS history_ai AFTER INSERT ON history BEGIN
+                INSERT INTO history_fts(rowid, text) VALUES (new.rowid, new.text);
+            END;
+            CREATE TRIGGER IF NOT EXISTS history_ad AFTER DELETE ON history BEGIN
+                INSERT INTO history_fts(history_fts, rowid, text) VALUES ('delete', old.rowid, old.text);
+            END;",
+        )
+        .context("Failed to initialize history schema")?;
+
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Inserts a completed session, then prunes down to `config.max_entries`
+    /// rows and/or `config.retention_days` age, oldest first. Errors are
+    /// logged by the caller, not propagated - history is best-effort, like
+    /// `Daemon::archive_recording`.
+    pub fn record(&self, entry: &HistoryEntry, config: &HistoryConfig) -> Result<()> {
+        let created_at = entry
+            .created_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO history (id, text, confidence, created_at, duration_secs, model_name)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.id.to_string(),
+                entry.text,
+                entry.confidence,
+                created_at,
+                entry.duration_secs,
+                entry.model_name,
+            ],
+        )
+        .context("Failed to insert history entry")?;
+
+        if config.retention_days > 0 {
+            let cutoff = created_at - config.retention_days as i64 * 86_400;
+            conn.execute("DELETE FROM history WHERE created_at < ?1", params![cutoff])
+                .context("Failed to prune expired history entries")?;
+        }
+
+        if config.max_entries > 0 {
+            conn.execute(
+                "DELETE FROM history WHERE rowid NOT IN (
+                    SELECT rowid FROM history ORDER BY created_at DESC LIMIT ?1
+                )",
+                params![config.max_entries as i64],
+            )
+            .context("Failed to prune history beyond max_entries")?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists the `limit` most recent entries, or (when `query` is set) the
+    /// `limit` best full-text matches ranked by FTS5's default bm25 scoring.
+    pub fn list(&self, limit: usize, query: Option<&str>) -> Result<Vec<HistoryEntry>> {
+        let conn = self.conn.lock().unwrap();
+
+        let entries = match query {
+            Some(query) => {
+                let mut stmt = conn.prepare(
+                    "SELECT h.id, h.text, h.confidence, h.created_at, h.duration_secs, h.model_name
+                     FROM history_fts f JOIN history h ON h.rowid = f.rowid
+                     WHERE history_fts MATCH ?1
+                     ORDER BY rank LIMIT ?2",
+                )?;
+                stmt.query_map(params![query, limit as i64], row_to_entry)?
+                    .collect::<rusqlite::Result<Vec<_>>>()?
+            }
+            None => {
+                let mut stmt = conn.prepare(
+                    "SELECT id, text, confidence, created_at, duration_secs, model_name
+                     FROM history ORDER BY created_at DESC LIMIT ?1",
+                )?;
+                stmt.query_map(params![limit as i64], row_to_entry)?
+                    .collect::<rusqlite::Result<Vec<_>>>()?
+            }
+        };
+
+        Ok(entries)
+    }
+
+    pub fn get(&self, id: Uuid) -> Result<Option<HistoryEntry>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT id, text, confidence, created_at, duration_secs, model_name
+             FROM history WHERE id = ?1",
+            params![id.to_string()],
+            row_to_entry,
+        )
+        .optional()
+        .context("Failed to fetch history entry")
+    }
+
+    /// Returns whether a row was actually deleted, so the caller can tell a
+    /// missing id apart from a successful delete.
+    pub fn delete(&self, id: Uuid) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let affected = conn
+            .execute("DELETE FROM history WHERE id = ?1", params![id.to_string()])
+            .context("Failed to delete history entry")?;
+        Ok(affected > 0)
+    }
+}
+
+fn row_to_entry(row: &Row) -> rusqlite::Result<HistoryEntry> {
+    let id: String = row.get(0)?;
+    let created_at: i64 = row.get(3)?;
+
+    Ok(HistoryEntry {
+        id: Uuid::parse_str(&id).unwrap_or_default(),
+        text: row.get(1)?,
+        confidence: row.get(2)?,
+        created_at: UNIX_EPOCH + std::time::Duration::from_secs(created_at.max(0) as u64),
+        duration_secs: row.get(4)?,
+        model_name: row.get(5)?,
+    })
+}
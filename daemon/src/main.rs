@@ -1,13 +1,22 @@
 use anyhow::Result;
 use log::{info, error, warn};
 use shared::{Config, ClientMessage, DaemonMessage, protocol};
-use tokio::net::{UnixListener, UnixStream};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, UnixListener};
+use tokio::sync::mpsc;
 use tokio::fs;
 use std::sync::Arc;
 use std::collections::HashMap;
 use uuid::Uuid;
 
 mod daemon;
+mod denoise;
+mod history;
+mod metrics;
+mod opus_codec;
+mod recording;
+mod spectrum;
+mod vad;
 mod whisper;
 
 use daemon::Daemon;
@@ -32,8 +41,21 @@ async fn main() -> Result<()> {
     info!("IPC server listening on {:?}", config.ipc.socket_path);
     
     // Initialize daemon state
-    let daemon = Arc::new(Daemon::new(config)?);
-    
+    let daemon = Arc::new(Daemon::new(config.clone())?);
+
+    // Also listen on TCP when configured, for a client connecting via
+    // `--connect tcp://host:port` from another machine
+    // (shared::transport::ConnectTarget::Tcp). The Unix socket always stays
+    // up alongside it.
+    if let Some(tcp_bind) = config.ipc.tcp_bind.clone() {
+        let daemon = daemon.clone();
+        tokio::spawn(async move {
+            if let Err(e) = run_tcp_listener(tcp_bind, daemon).await {
+                error!("TCP listener error: {}", e);
+            }
+        });
+    }
+
     // Accept client connections
     loop {
         match listener.accept().await {
@@ -52,24 +74,91 @@ async fn main() -> Result<()> {
     }
 }
 
-async fn handle_client(mut stream: UnixStream, daemon: Arc<Daemon>) -> Result<()> {
-    info!("New client connected");
-    
+async fn run_tcp_listener(tcp_bind: String, daemon: Arc<Daemon>) -> Result<()> {
+    let listener = TcpListener::bind(&tcp_bind).await?;
+    info!("IPC server also listening on tcp://{}", tcp_bind);
+
     loop {
-        match protocol::receive_message::<ClientMessage>(&mut stream).await {
-            Ok(message) => {
-                let response = daemon.handle_message(message).await;
-                if let Err(e) = protocol::send_message(&mut stream, &response).await {
-                    error!("Failed to send response to client: {}", e);
+        match listener.accept().await {
+            Ok((stream, addr)) => {
+                info!("New TCP client connected from {}", addr);
+                let daemon = daemon.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_client(stream, daemon).await {
+                        error!("TCP client handler error: {}", e);
+                    }
+                });
+            }
+            Err(e) => {
+                error!("Failed to accept TCP client connection: {}", e);
+            }
+        }
+    }
+}
+
+async fn handle_client<S: AsyncRead + AsyncWrite + Unpin + Send + 'static>(stream: S, daemon: Arc<Daemon>) -> Result<()> {
+    info!("New client connected");
+
+    let (mut read_half, mut write_half) = tokio::io::split(stream);
+    let mut model_events = daemon.subscribe_model_events();
+    let mut spectrum_events = daemon.subscribe_spectrum_events();
+
+    // `protocol::receive_message` isn't cancel-safe - it does two sequential
+    // read_exact calls - so it can never be raced directly against
+    // `model_events.recv()` in the select below: if the model-event branch
+    // won mid-read, the bytes already consumed off the socket would be lost
+    // and every later read on this connection would desync. A dedicated task
+    // owns the only call to `receive_message` and forwards whole, decoded
+    // messages over an mpsc channel instead, whose `recv()` is cancel-safe.
+    let (client_tx, mut client_rx) = mpsc::channel(8);
+    tokio::spawn(async move {
+        loop {
+            match protocol::receive_message::<_, ClientMessage>(&mut read_half).await {
+                Ok(message) => {
+                    if client_tx.send(message).await.is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    info!("Client disconnected: {}", e);
                     break;
                 }
             }
-            Err(e) => {
-                info!("Client disconnected: {}", e);
-                break;
+        }
+    });
+
+    loop {
+        tokio::select! {
+            message = client_rx.recv() => {
+                match message {
+                    Some(message) => {
+                        let response = daemon.handle_message(message).await;
+                        if let Err(e) = protocol::send_message(&mut write_half, &response).await {
+                            error!("Failed to send response to client: {}", e);
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            }
+            event = model_events.recv() => {
+                if let Ok(message) = event {
+                    if let Err(e) = protocol::send_message(&mut write_half, &message).await {
+                        error!("Failed to send model event to client: {}", e);
+                        break;
+                    }
+                }
+            }
+            event = spectrum_events.recv() => {
+                if let Ok(message) = event {
+                    if let Err(e) = protocol::send_message(&mut write_half, &message).await {
+                        error!("Failed to send spectrum event to client: {}", e);
+                        break;
+                    }
+                }
             }
         }
     }
-    
+
     Ok(())
 }
\ No newline at end of file
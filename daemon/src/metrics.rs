@@ -0,0 +1,140 @@
+#![cfg(feature = "metrics")]
+
+//! Optional operational telemetry, pushed to a Prometheus Pushgateway. Only
+//! compiled in when the daemon is built with `--features metrics`, mirroring
+//! Spoticord's opt-in `metrics` feature - everyone else pays nothing for it.
+//! Recording a value (a counter bump, a histogram observation) is cheap and
+//! synchronous; the push itself runs on its own background task so a slow or
+//! unreachable Pushgateway never adds latency to transcription.
+
+use log::error;
+use prometheus::{Histogram, HistogramOpts, IntCounter, IntGauge, Registry};
+use shared::{MetricsConfig, TranscriptionSession};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct Metrics {
+    registry: Registry,
+    recordings_started: IntCounter,
+    recordings_stopped: IntCounter,
+    transcriptions_completed: IntCounter,
+    model_loads: IntCounter,
+    model_unloads: IntCounter,
+    active_sessions: IntGauge,
+    transcription_latency_seconds: Histogram,
+    transcribed_word_count: Histogram,
+    transcription_confidence: Histogram,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let recordings_started = IntCounter::new("dictation_recordings_started_total", "Recordings started").unwrap();
+        let recordings_stopped = IntCounter::new("dictation_recordings_stopped_total", "Recordings stopped").unwrap();
+        let transcriptions_completed = IntCounter::new("dictation_transcriptions_completed_total", "Transcriptions completed successfully").unwrap();
+        let model_loads = IntCounter::new("dictation_model_loads_total", "Whisper model loads").unwrap();
+        let model_unloads = IntCounter::new("dictation_model_unloads_total", "Whisper model unloads after sitting idle").unwrap();
+        let active_sessions = IntGauge::new("dictation_active_sessions", "Currently active recording sessions").unwrap();
+        let transcription_latency_seconds = Histogram::with_opts(
+            HistogramOpts::new("dictation_transcription_latency_seconds", "Wall-clock time from session start to final transcript")
+        ).unwrap();
+        let transcribed_word_count = Histogram::with_opts(
+            HistogramOpts::new("dictation_transcribed_word_count", "Word count of completed transcriptions")
+                .buckets(vec![1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0])
+        ).unwrap();
+        let transcription_confidence = Histogram::with_opts(
+            HistogramOpts::new("dictation_transcription_confidence", "Reported confidence of completed transcriptions")
+                .buckets(vec![0.1, 0.3, 0.5, 0.7, 0.8, 0.9, 0.95, 1.0])
+        ).unwrap();
+
+        for metric in [
+            Box::new(recordings_started.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(recordings_stopped.clone()),
+            Box::new(transcriptions_completed.clone()),
+            Box::new(model_loads.clone()),
+            Box::new(model_unloads.clone()),
+            Box::new(active_sessions.clone()),
+            Box::new(transcription_latency_seconds.clone()),
+            Box::new(transcribed_word_count.clone()),
+            Box::new(transcription_confidence.clone()),
+        ] {
+            registry.register(metric).expect("metric names are unique and registered once");
+        }
+
+        Self {
+            registry,
+            recordings_started,
+            recordings_stopped,
+            transcriptions_completed,
+            model_loads,
+            model_unloads,
+            active_sessions,
+            transcription_latency_seconds,
+            transcribed_word_count,
+            transcription_confidence,
+        }
+    }
+
+    pub fn record_recording_started(&self) {
+        self.recordings_started.inc();
+        self.active_sessions.inc();
+    }
+
+    pub fn record_recording_stopped(&self) {
+        self.recordings_stopped.inc();
+        self.active_sessions.dec();
+    }
+
+    pub fn record_model_loaded(&self) {
+        self.model_loads.inc();
+    }
+
+    pub fn record_model_unloaded(&self) {
+        self.model_unloads.inc();
+    }
+
+    pub fn record_transcription_complete(&self, session: &TranscriptionSession, latency: Duration) {
+        self.transcriptions_completed.inc();
+        self.transcription_latency_seconds.observe(latency.as_secs_f64());
+        self.transcribed_word_count.observe(session.text.split_whitespace().count() as f64);
+        if let Some(confidence) = session.confidence {
+            self.transcription_confidence.observe(confidence as f64);
+        }
+    }
+
+    /// Spawns a background task that pushes the current metric values to
+    /// `config.pushgateway_url` every `config.push_interval_seconds`, for as
+    /// long as `config.enabled` is true. Does nothing otherwise - `Metrics`
+    /// still gets instrumented either way, it just never leaves the process.
+    pub fn spawn_pusher(self: Arc<Self>, config: MetricsConfig) {
+        if !config.enabled {
+            return;
+        }
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(config.push_interval_seconds.max(1)));
+            loop {
+                interval.tick().await;
+
+                let metrics = self.clone();
+                let url = config.pushgateway_url.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    prometheus::push_metrics(
+                        "dictation_daemon",
+                        prometheus::labels! {},
+                        &url,
+                        metrics.registry.gather(),
+                        None,
+                    )
+                }).await;
+
+                match result {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => error!("Failed to push metrics to {}: {}", url, e),
+                    Err(e) => error!("Metrics push task panicked: {}", e),
+                }
+            }
+        });
+    }
+}
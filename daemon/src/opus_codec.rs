@@ -0,0 +1,40 @@
+// Decodes the Opus frames carried by `ClientMessage::StreamAudioOpus` back
+// into f32 PCM so the rest of the daemon (VAD, Whisper) never has to know
+// the wire format changed.
+
+use anyhow::{Context, Result};
+use opus::{Channels, Decoder};
+
+pub struct OpusDecoderState {
+    decoder: Decoder,
+    channels: usize,
+}
+
+impl OpusDecoderState {
+    pub fn new(sample_rate: u32, channels: u16) -> Result<Self> {
+        let opus_channels = if channels == 1 { Channels::Mono } else { Channels::Stereo };
+        let decoder = Decoder::new(sample_rate, opus_channels)
+            .context("Failed to create Opus decoder")?;
+
+        Ok(Self {
+            decoder,
+            channels: channels as usize,
+        })
+    }
+
+    /// Decode a batch of 20ms frames into one contiguous, interleaved f32 PCM buffer.
+    pub fn decode_frames(&mut self, frames: &[Vec<u8>]) -> Result<Vec<f32>> {
+        let mut pcm = Vec::new();
+        // 20ms at up to 48kHz is at most 960 samples/channel; size generously.
+        let mut scratch = vec![0f32; 5760 * self.channels];
+
+        for frame in frames {
+            let decoded = self.decoder
+                .decode_float(frame, &mut scratch, false)
+                .context("Failed to decode Opus frame")?;
+            pcm.extend_from_slice(&scratch[..decoded * self.channels]);
+        }
+
+        Ok(pcm)
+    }
+}
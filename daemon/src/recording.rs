@@ -0,0 +1,110 @@
+// Persists a finished session's raw PCM to disk as WAV so a client can ask
+// the daemon to keep a copy of what it just transcribed.
+
+use anyhow::{Context, Result};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Below this RMS energy a capture is considered silence/empty and is never
+/// written to disk.
+const SILENCE_RMS_THRESHOLD: f32 = 0.005;
+
+pub fn is_effectively_empty(samples: &[f32]) -> bool {
+    if samples.is_empty() {
+        return true;
+    }
+
+    let sum_sq: f32 = samples.iter().map(|s| s * s).sum();
+    let rms = (sum_sq / samples.len() as f32).sqrt();
+    rms < SILENCE_RMS_THRESHOLD
+}
+
+pub fn save_wav(path: &Path, samples: &[f32], sample_rate: u32, channels: u16) -> Result<()> {
+    let spec = WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: SampleFormat::Float,
+    };
+
+    let mut writer = WavWriter::create(path, spec)
+        .with_context(|| format!("Failed to create WAV file at {:?}", path))?;
+
+    for &sample in samples {
+        writer.write_sample(sample)
+            .context("Failed to write audio sample")?;
+    }
+
+    writer.finalize().context("Failed to finalize WAV file")?;
+    Ok(())
+}
+
+/// Sidecar metadata written alongside an archived recording, so a stored WAV
+/// can be re-transcribed or audited without re-deriving what model/language
+/// produced its transcript.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingSidecar {
+    pub transcript: String,
+    pub timestamp: SystemTime,
+    pub model_path: PathBuf,
+    pub language: Option<String>,
+}
+
+pub fn save_sidecar(path: &Path, sidecar: &RecordingSidecar) -> Result<()> {
+    let content = serde_yaml::to_string(sidecar).context("Failed to serialize recording sidecar")?;
+    std::fs::write(path, content)
+        .with_context(|| format!("Failed to write sidecar file at {:?}", path))?;
+    Ok(())
+}
+
+/// Encodes `samples` as 16-bit PCM WAV, the format archived recordings are
+/// stored in (half the size of the float32 WAV `save_wav` writes for
+/// one-off `SaveRecording` requests).
+pub fn save_wav_pcm16(path: &Path, samples: &[f32], sample_rate: u32, channels: u16) -> Result<()> {
+    let spec = WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+
+    let mut writer = WavWriter::create(path, spec)
+        .with_context(|| format!("Failed to create WAV file at {:?}", path))?;
+
+    for &sample in samples {
+        let pcm_sample = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        writer.write_sample(pcm_sample)
+            .context("Failed to write audio sample")?;
+    }
+
+    writer.finalize().context("Failed to finalize WAV file")?;
+    Ok(())
+}
+
+/// Loads a WAV file back into f32 samples for re-transcription, regardless
+/// of whether it was written as float32 (`save_wav`) or 16-bit PCM
+/// (`save_wav_pcm16`).
+pub fn load_wav(path: &Path) -> Result<(Vec<f32>, u32, u16)> {
+    let mut reader = WavReader::open(path)
+        .with_context(|| format!("Failed to open WAV file at {:?}", path))?;
+    let spec = reader.spec();
+
+    let samples: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<std::result::Result<_, _>>()
+            .context("Failed to read float WAV samples")?,
+        SampleFormat::Int => {
+            let max = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / max))
+                .collect::<std::result::Result<_, _>>()
+                .context("Failed to read integer WAV samples")?
+        }
+    };
+
+    Ok((samples, spec.sample_rate, spec.channels))
+}
@@ -0,0 +1,71 @@
+// Computes a smoothed, log-banded magnitude spectrum from the latest block
+// of audio so the popup can render a multi-band level meter instead of a
+// single bar. This is pure feedback for the UI - it never feeds back into
+// VAD or transcription.
+
+use rustfft::{num_complex::Complex32, FftPlanner};
+
+const FFT_SIZE: usize = 1024;
+const NUM_BANDS: usize = 24;
+const SMOOTHING_ALPHA: f32 = 0.3;
+
+pub struct SpectrumAnalyzer {
+    planner: FftPlanner<f32>,
+    bands: Vec<f32>,
+}
+
+impl SpectrumAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            planner: FftPlanner::new(),
+            bands: vec![0.0; NUM_BANDS],
+        }
+    }
+
+    /// Run a Hann-windowed FFT over the last `FFT_SIZE` samples of `data`
+    /// (zero-padded if shorter), group bins into `NUM_BANDS` logarithmically
+    /// spaced bands, convert to dB, and exponentially smooth against the
+    /// previous call's output so the display doesn't flicker block to block.
+    pub fn analyze(&mut self, data: &[f32]) -> Vec<f32> {
+        let mut buf = vec![Complex32::new(0.0, 0.0); FFT_SIZE];
+        let start = data.len().saturating_sub(FFT_SIZE);
+        let window = &data[start..];
+
+        for (i, &sample) in window.iter().enumerate() {
+            let hann = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (FFT_SIZE - 1) as f32).cos();
+            buf[i] = Complex32::new(sample * hann, 0.0);
+        }
+
+        let fft = self.planner.plan_fft_forward(FFT_SIZE);
+        fft.process(&mut buf);
+
+        // Only the first half carries distinct frequency content for a
+        // real-valued input.
+        let half = FFT_SIZE / 2;
+        for band in 0..NUM_BANDS {
+            let lo = band_edge(band, half);
+            let hi = band_edge(band + 1, half).max(lo + 1).min(half);
+
+            let mut mag_sum = 0.0f32;
+            for bin in lo..hi {
+                mag_sum += (buf[bin].re * buf[bin].re + buf[bin].im * buf[bin].im).sqrt();
+            }
+            let mag = mag_sum / (hi - lo) as f32;
+
+            let db = 20.0 * (mag + 1e-6).log10();
+            // Normalize a roughly [-80dB, 0dB] range into [0, 1].
+            let normalized = ((db + 80.0) / 80.0).clamp(0.0, 1.0);
+            let normalized = if normalized.is_nan() { 0.0 } else { normalized };
+
+            self.bands[band] = SMOOTHING_ALPHA * normalized + (1.0 - SMOOTHING_ALPHA) * self.bands[band];
+        }
+
+        self.bands.clone()
+    }
+}
+
+/// Logarithmic bin spacing from bin 1 (skip DC) up to `half`.
+fn band_edge(band: usize, half: usize) -> usize {
+    let t = band as f32 / NUM_BANDS as f32;
+    (half as f32).powf(t).max(1.0) as usize
+}
@@ -0,0 +1,142 @@
+// Energy-plus-zero-crossing voice-activity detector used to drive the
+// daemon's streaming and continuous transcription paths. Operates on 16 kHz
+// mono f32 samples.
+
+const SAMPLE_RATE: usize = 16_000;
+const FRAME_SAMPLES: usize = 480; // ~30ms @ 16kHz
+const FRAME_MS: u64 = (FRAME_SAMPLES * 1000 / SAMPLE_RATE) as u64;
+const PARTIAL_INTERVAL_SAMPLES: usize = SAMPLE_RATE; // re-transcribe the growing segment every ~1s
+
+/// Something the VAD noticed since the last call to `process`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VadEvent {
+    None,
+    SpeechStarted,
+    PartialReady { start: usize, end: usize },
+    SpeechEnded { start: usize, end: usize },
+}
+
+pub struct VoiceActivityDetector {
+    sensitivity: f32,
+    energy_threshold: f32,
+    hangover_frames: u32,
+    noise_floor: f32,
+    in_speech: bool,
+    silence_frames: u32,
+    segment_start: usize,
+    last_partial_at: usize,
+    processed: usize,
+}
+
+impl VoiceActivityDetector {
+    /// `energy_threshold` is an extra absolute floor added on top of the
+    /// sensitivity-scaled noise floor - 0.0 leaves behavior identical to
+    /// sensitivity alone. `silence_ms` is how long a frame must read as
+    /// non-speech before an in-progress segment is finalized.
+    pub fn new(sensitivity: f32, energy_threshold: f32, silence_ms: u64) -> Self {
+        let hangover_frames = (silence_ms / FRAME_MS).max(1) as u32;
+
+        Self {
+            sensitivity: sensitivity.clamp(0.0, 1.0),
+            energy_threshold,
+            hangover_frames,
+            noise_floor: 0.001,
+            in_speech: false,
+            silence_frames: 0,
+            segment_start: 0,
+            last_partial_at: 0,
+            processed: 0,
+        }
+    }
+
+    pub fn set_sensitivity(&mut self, sensitivity: f32) {
+        self.sensitivity = sensitivity.clamp(0.0, 1.0);
+    }
+
+    /// 0.0 -> very permissive (low bar over the noise floor), 1.0 -> strict.
+    fn k(&self) -> f32 {
+        2.0 + 6.0 * self.sensitivity
+    }
+
+    /// Scan any whole frames appended to `data` since the last call and
+    /// return the single most relevant event. Only one event is reported per
+    /// call; callers that need every frame's worth of activity should call
+    /// this once per newly-appended chunk.
+    pub fn process(&mut self, data: &[f32]) -> VadEvent {
+        let mut event = VadEvent::None;
+
+        while self.processed + FRAME_SAMPLES <= data.len() {
+            let frame = &data[self.processed..self.processed + FRAME_SAMPLES];
+            let energy = rms_energy(frame);
+            let zcr = zero_crossing_rate(frame);
+            let threshold = self.noise_floor * self.k() + self.energy_threshold;
+            // A high zero-crossing rate alongside low energy is typically
+            // hiss, not speech, so require a plausible voiced rate too.
+            let is_speech = energy > threshold && zcr < 0.5;
+
+            if is_speech {
+                if !self.in_speech {
+                    self.in_speech = true;
+                    self.segment_start = self.processed;
+                    self.last_partial_at = self.processed;
+                    event = VadEvent::SpeechStarted;
+                }
+                self.silence_frames = 0;
+            } else {
+                // Only quiet frames pull the noise floor down, so a sustained
+                // loud region doesn't drag the threshold up with it.
+                self.noise_floor = self.noise_floor * 0.95 + energy * 0.05;
+
+                if self.in_speech {
+                    self.silence_frames += 1;
+                    if self.silence_frames >= self.hangover_frames {
+                        let end = self.processed + FRAME_SAMPLES;
+                        self.in_speech = false;
+                        self.silence_frames = 0;
+                        self.processed = end;
+                        return VadEvent::SpeechEnded {
+                            start: self.segment_start,
+                            end,
+                        };
+                    }
+                }
+            }
+
+            self.processed += FRAME_SAMPLES;
+        }
+
+        if event == VadEvent::None
+            && self.in_speech
+            && self.processed - self.last_partial_at >= PARTIAL_INTERVAL_SAMPLES
+        {
+            self.last_partial_at = self.processed;
+            event = VadEvent::PartialReady {
+                start: self.segment_start,
+                end: self.processed,
+            };
+        }
+
+        event
+    }
+
+    /// Shift internal sample offsets back after the caller drops `trimmed`
+    /// samples off the front of the backing buffer.
+    pub fn trim(&mut self, trimmed: usize) {
+        self.processed = self.processed.saturating_sub(trimmed);
+        self.segment_start = self.segment_start.saturating_sub(trimmed);
+        self.last_partial_at = self.last_partial_at.saturating_sub(trimmed);
+    }
+}
+
+fn rms_energy(frame: &[f32]) -> f32 {
+    let sum_sq: f32 = frame.iter().map(|s| s * s).sum();
+    (sum_sq / frame.len() as f32).sqrt()
+}
+
+fn zero_crossing_rate(frame: &[f32]) -> f32 {
+    if frame.len() < 2 {
+        return 0.0;
+    }
+    let crossings = frame.windows(2).filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0)).count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
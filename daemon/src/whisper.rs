@@ -1,10 +1,22 @@
 use anyhow::Result;
 use log::{info, error};
 use shared::WhisperConfig;
+use std::path::Path;
 use std::time::{SystemTime, Duration};
 use std::sync::Arc;
 use whisper_rs::{WhisperContext, WhisperContextParameters, FullParams, SamplingStrategy};
 
+use crate::recording;
+
+/// Builds the context parameters for a model load attempt. `gpu_device` is
+/// only meaningful when `use_gpu` is set and more than one GPU is present.
+fn whisper_params(use_gpu: bool, gpu_device: u32) -> WhisperContextParameters {
+    let mut params = WhisperContextParameters::default();
+    params.use_gpu(use_gpu);
+    params.gpu_device(gpu_device as i32);
+    params
+}
+
 pub struct WhisperManager {
     config: WhisperConfig,
     model: Option<Arc<WhisperContext>>,
@@ -44,34 +56,60 @@ impl WhisperManager {
         }
         
         info!("Loading Whisper model from {:?}", self.config.model_path);
-        
+
         // Load the model using whisper-rs in a blocking task
         let model_path = self.config.model_path.clone();
-        let ctx = tokio::task::spawn_blocking(move || {
-            let params = WhisperContextParameters::default();
-            WhisperContext::new_with_params(&model_path.to_string_lossy(), params)
-        }).await??;
-        
+        let use_gpu = self.config.use_gpu;
+        let gpu_device = self.config.gpu_device;
+
+        let ctx = {
+            let model_path_clone = model_path.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                info!(
+                    "Loading Whisper model on {}",
+                    if use_gpu { "GPU" } else { "CPU" }
+                );
+                WhisperContext::new_with_params(&model_path_clone.to_string_lossy(), whisper_params(use_gpu, gpu_device))
+            }).await?;
+
+            match result {
+                Ok(ctx) => ctx,
+                Err(e) if use_gpu => {
+                    error!("GPU initialization failed ({:?}), falling back to CPU", e);
+                    tokio::task::spawn_blocking(move || {
+                        WhisperContext::new_with_params(&model_path.to_string_lossy(), whisper_params(false, gpu_device))
+                    }).await??
+                }
+                Err(e) => return Err(e.into()),
+            }
+        };
+
         self.model = Some(Arc::new(ctx));
-        
+
         info!("Whisper model loaded successfully");
         Ok(())
     }
     
-    pub async fn unload_if_timeout(&mut self) -> Result<()> {
-        if let Some(last_used) = self.last_used {
-            let timeout_duration = Duration::from_secs(self.config.model_timeout_seconds);
-            
-            if last_used.elapsed().unwrap_or(Duration::ZERO) > timeout_duration {
-                info!("Unloading Whisper model due to timeout");
-                self.model = None;
-                self.last_used = None;
-            }
+    /// Release the loaded model. The daemon calls this once it's decided the
+    /// model has been idle for too long; this type doesn't track idleness
+    /// itself since "idle" depends on active sessions, not just last use.
+    pub fn unload(&mut self) {
+        if self.model.is_some() {
+            info!("Unloading Whisper model");
         }
-        
-        Ok(())
+        self.model = None;
+        self.last_used = None;
     }
-    
+
+    pub fn idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.model_timeout_seconds)
+    }
+
+    pub fn last_used(&self) -> Option<SystemTime> {
+        self.last_used
+    }
+
+
     pub async fn transcribe_audio(&mut self, audio_data: &[f32]) -> Result<String> {
         let ctx = self.model.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Whisper model not loaded"))?;
@@ -102,6 +140,17 @@ impl WhisperManager {
         }
     }
     
+    /// Loads an archived recording from `wav_path` and re-runs transcription
+    /// against the currently loaded model, so a user who switches to a
+    /// bigger/better model can reprocess old recordings instead of
+    /// re-speaking them. Assumes the WAV is 16 kHz mono, which is true for
+    /// anything `Daemon::archive_recording` wrote, since `AudioBuffer` only
+    /// ever holds resampled 16 kHz mono audio.
+    pub async fn retranscribe(&mut self, wav_path: &Path) -> Result<String> {
+        let (samples, _sample_rate, _channels) = recording::load_wav(wav_path)?;
+        self.transcribe_audio(&samples).await
+    }
+
     async fn transcribe_blocking(
         ctx: Arc<WhisperContext>,
         audio_data: Vec<f32>,
@@ -5,6 +5,8 @@ use std::fs;
 pub struct Config {
     pub whisper: Option<WhisperConfig>,
     pub ui: Option<UIConfig>,
+    pub audio: Option<AudioConfig>,
+    pub output: Option<OutputConfig>,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -13,6 +15,18 @@ pub struct WhisperConfig {
     pub model_timeout_seconds: Option<u32>,
     pub language: Option<String>,
     pub fuzzy_match_threshold: Option<f32>,
+    pub use_gpu: Option<bool>,
+    pub gpu_device: Option<u32>,
+    pub denoise: Option<bool>,
+    pub denoise_strength: Option<f32>,
+    pub continuous: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct AudioConfig {
+    pub device: Option<String>,
+    pub save_recordings: Option<bool>,
+    pub recordings_dir: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -21,6 +35,15 @@ pub struct UIConfig {
     pub auto_close_after_copy: Option<bool>,
 }
 
+/// Which `crate::output::OutputBackend` a finished dictation is sent to:
+/// "wayland" (wl-copy), "x11" (xclip/xsel), "macos" (pbcopy), or "type" to
+/// inject it directly into the focused window (wtype/ydotool) instead of
+/// copying it at all.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct OutputConfig {
+    pub backend: Option<String>,
+}
+
 impl Config {
     pub fn auto_copy(&self) -> bool {
         self.ui.as_ref()
@@ -57,6 +80,64 @@ impl Config {
             .and_then(|w| w.fuzzy_match_threshold)
             .unwrap_or(0.8)
     }
+
+    pub fn use_gpu(&self) -> bool {
+        self.whisper.as_ref()
+            .and_then(|w| w.use_gpu)
+            .unwrap_or(false)
+    }
+
+    pub fn gpu_device(&self) -> u32 {
+        self.whisper.as_ref()
+            .and_then(|w| w.gpu_device)
+            .unwrap_or(0)
+    }
+
+    pub fn denoise(&self) -> bool {
+        self.whisper.as_ref()
+            .and_then(|w| w.denoise)
+            .unwrap_or(false)
+    }
+
+    pub fn denoise_strength(&self) -> f32 {
+        self.whisper.as_ref()
+            .and_then(|w| w.denoise_strength)
+            .unwrap_or(0.5)
+    }
+
+    /// Whether the daemon session behind this popup keeps running and
+    /// segmenting speech on its own, emitting one `TranscriptionComplete` per
+    /// utterance instead of requiring an explicit "Stop Recording" click.
+    pub fn continuous(&self) -> bool {
+        self.whisper.as_ref()
+            .and_then(|w| w.continuous)
+            .unwrap_or(false)
+    }
+
+    /// `None` means "use the system default input device".
+    pub fn device(&self) -> Option<String> {
+        self.audio.as_ref().and_then(|a| a.device.clone())
+    }
+
+    pub fn save_recordings(&self) -> bool {
+        self.audio.as_ref()
+            .and_then(|a| a.save_recordings)
+            .unwrap_or(false)
+    }
+
+    pub fn recordings_dir(&self) -> String {
+        self.audio.as_ref()
+            .and_then(|a| a.recordings_dir.clone())
+            .unwrap_or_else(|| "recordings".to_string())
+    }
+
+    /// Defaults to "wayland" to match this popup's previous hardcoded
+    /// wl-copy behavior when unset.
+    pub fn output_backend(&self) -> String {
+        self.output.as_ref()
+            .and_then(|o| o.backend.clone())
+            .unwrap_or_else(|| "wayland".to_string())
+    }
 }
 
 pub fn load_config() -> Config {
@@ -1,23 +1,83 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use eframe::egui;
 use shared::ipc::{ClientMessage, DaemonMessage, protocol};
+use shared::transport::{ConnectTarget, Transport};
 use std::sync::mpsc;
-use tokio::net::UnixStream;
+use std::time::Duration;
 use uuid::Uuid;
 
+mod config;
+mod output;
+mod settings_app;
+
+use settings_app::SettingsApp;
+
+const DEFAULT_SOCKET_PATH: &str = "/tmp/dictation.sock";
+
+/// Reconstructs the `--connect` argument a resolved `ConnectTarget` came
+/// from, so a spawned subprocess (e.g. `--settings`) reaches the same daemon.
+fn connect_addr(connect: &ConnectTarget) -> String {
+    match connect {
+        ConnectTarget::Unix(path) => path.display().to_string(),
+        ConnectTarget::Tcp(addr) => format!("tcp://{}", addr),
+    }
+}
+
+// How often to ping the daemon with GetStatus while listening for
+// transcription, so a silently dead daemon (process gone but socket not yet
+// torn down) is noticed within a bounded interval instead of only on the next read.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Parser)]
 #[command(name = "dictation-popup")]
 #[command(about = "Voice dictation interface")]
 struct Args {
     #[arg(short, long)]
     text: Option<String>,
+
+    /// Run headless: stream transcription to stdout instead of showing a GUI window
+    #[arg(long)]
+    stream: bool,
+
+    /// Open the settings window instead of starting a recording
+    #[arg(long)]
+    settings: bool,
+
+    /// Daemon address to connect to: a Unix socket path, or `tcp://host:port`
+    /// for a daemon running on another machine. Defaults to the local Unix socket.
+    #[arg(long)]
+    connect: Option<String>,
 }
 
 fn main() -> Result<()> {
     env_logger::init();
 
     let args = Args::parse();
+    let connect = match &args.connect {
+        Some(addr) => ConnectTarget::parse(addr),
+        None => ConnectTarget::unix_default(DEFAULT_SOCKET_PATH.into()),
+    };
+
+    if args.stream {
+        let rt = tokio::runtime::Runtime::new()?;
+        return rt.block_on(run_stream_mode(connect));
+    }
+
+    if args.settings {
+        let options = eframe::NativeOptions {
+            viewport: egui::ViewportBuilder::default()
+                .with_inner_size([500.0, 600.0])
+                .with_title("Voice Dictation Settings"),
+            ..Default::default()
+        };
+
+        return eframe::run_native(
+            "Voice Dictation Settings",
+            options,
+            Box::new(|_cc| Ok(Box::new(SettingsApp::new(connect)))),
+        ).map_err(|e| anyhow::anyhow!("Failed to run settings window: {}", e));
+    }
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -29,12 +89,121 @@ fn main() -> Result<()> {
     eframe::run_native(
         "Voice Dictation",
         options,
-        Box::new(|_cc| Ok(Box::new(DictationApp::new(args.text)))),
+        Box::new(|_cc| Ok(Box::new(DictationApp::new(args.text, connect)))),
     ).map_err(|e| anyhow::anyhow!("Failed to run egui app: {}", e))
 }
 
+/// Headless counterpart to the egui app: drives the same recording session
+/// but prints transcription straight to stdout instead of painting a window,
+/// so `dictation-popup --stream` can sit in a shell pipeline. A Ctrl-C stops
+/// the recording on the daemon side cleanly rather than just killing the process.
+async fn run_stream_mode(connect: ConnectTarget) -> Result<()> {
+    let mut stream = connect.connect().await
+        .context("Failed to connect to daemon; is it running?")?;
+
+    protocol::send_message(&mut stream, &ClientMessage::StartRecording).await?;
+    let session_id = loop {
+        match protocol::receive_message::<_, DaemonMessage>(&mut stream).await? {
+            DaemonMessage::RecordingStarted(session_id) => break session_id,
+            DaemonMessage::ModelLoading => eprintln!("Loading model..."),
+            DaemonMessage::ModelLoaded => eprintln!("Model loaded"),
+            DaemonMessage::Error(error) => anyhow::bail!("Daemon error: {}", error),
+            other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+        }
+    };
+
+    eprintln!("Recording (session {}). Press Ctrl-C to stop.", session_id);
+
+    // `protocol::receive_message` isn't cancel-safe (two sequential
+    // read_exact calls), so it can't be a direct branch of the select! below
+    // - a Ctrl-C winning the race mid-read would drop already-consumed bytes
+    // and desync the stream's framing. A background task owns the only read
+    // loop over `stream` and forwards whole decoded messages through an
+    // mpsc channel, whose recv() is cancel-safe to select against.
+    let (mut read_half, mut write_half) = tokio::io::split(stream);
+    let (msg_tx, mut msg_rx) = tokio::sync::mpsc::channel::<Result<DaemonMessage>>(8);
+    tokio::spawn(async move {
+        loop {
+            let message = protocol::receive_message::<_, DaemonMessage>(&mut read_half).await;
+            let is_err = message.is_err();
+            if msg_tx.send(message).await.is_err() || is_err {
+                break;
+            }
+        }
+    });
+
+    let mut sigint = Box::pin(tokio::signal::ctrl_c());
+    // `partial_text` is the daemon's cumulative text for the current
+    // in-progress speech segment (see merge_partial), not a delta, and it
+    // resets to empty at the start of each new segment - so naively
+    // print!()-ing it every update would reprint (and duplicate) everything
+    // already written to stdout. Track what's already been printed for the
+    // current segment and only print the new suffix.
+    let mut segment_printed = String::new();
+    loop {
+        tokio::select! {
+            message = msg_rx.recv() => {
+                match message {
+                    Some(message) => match message? {
+                        DaemonMessage::TranscriptionUpdate { session_id: msg_id, partial_text, is_final } => {
+                            if msg_id == session_id && !partial_text.is_empty() {
+                                if let Some(delta) = partial_text.strip_prefix(segment_printed.as_str()) {
+                                    print!("{}", delta);
+                                } else {
+                                    // Text doesn't extend what was already printed (e.g. a
+                                    // continuous-mode utterance was finalized without an
+                                    // is_final update, so the next segment starts over) -
+                                    // start a fresh line.
+                                    println!();
+                                    print!("{}", partial_text);
+                                }
+                                std::io::Write::flush(&mut std::io::stdout()).ok();
+                                if is_final {
+                                    println!();
+                                    segment_printed.clear();
+                                } else {
+                                    segment_printed = partial_text;
+                                }
+                            }
+                        }
+                        DaemonMessage::TranscriptionComplete(session) => {
+                            println!("{}", session.text);
+                            return Ok(());
+                        }
+                        DaemonMessage::RecordingStopped => return Ok(()),
+                        DaemonMessage::Error(error) => eprintln!("Daemon error: {}", error),
+                        _ => {}
+                    },
+                    None => return Ok(()),
+                }
+            }
+            _ = &mut sigint => {
+                eprintln!("\nStopping...");
+                protocol::send_message(&mut write_half, &ClientMessage::StopRecording(session_id)).await?;
+                loop {
+                    match msg_rx.recv().await {
+                        Some(Ok(DaemonMessage::TranscriptionComplete(session))) => {
+                            println!("{}", session.text);
+                            break;
+                        }
+                        Some(Ok(DaemonMessage::RecordingStopped)) => break,
+                        Some(Ok(_)) => continue,
+                        Some(Err(_)) | None => break,
+                    }
+                }
+                return Ok(());
+            }
+        }
+    }
+}
+
 struct DictationApp {
     text: String,
+    // Text already finalized (SpeechEnded) for earlier segments of the
+    // current recording. `text` is this plus whatever the in-progress
+    // segment's latest partial_text is - see the TranscriptionUpdate
+    // handler below for why it can't just be appended to `text` directly.
+    confirmed_segment_text: String,
     complete_text: String,
     recording_status: String,
     daemon_connected: bool,
@@ -43,11 +212,22 @@ struct DictationApp {
 
     // Real-time feedback state
     audio_level: f32,
+    spectrum: Vec<f32>,
     voice_active: bool,
     is_processing: bool,
+    muted_by_user: bool,
+
+    // History panel state
+    show_history: bool,
+    history_query: String,
+    history_entries: Vec<shared::HistoryEntry>,
 
     rx: mpsc::Receiver<UiMessage>,
     _tx: mpsc::Sender<UiMessage>, // Keep sender alive
+    connect: ConnectTarget,
+    output_backend: Box<dyn output::OutputBackend>,
+    config: config::Config,
+    should_close: bool,
 }
 
 #[derive(Debug)]
@@ -60,6 +240,7 @@ enum UiMessage {
 
     // Real-time feedback
     AudioLevel(f32),
+    Spectrum(Vec<f32>),
     VoiceActivityDetected,
     VoiceActivityEnded,
     ProcessingStarted,
@@ -67,21 +248,31 @@ enum UiMessage {
 
     // Session management
     SessionCleared,
+    Reconnecting,
+    RecordingSaved(std::path::PathBuf, f32), // path, duration_secs
+    Muted(bool),
     Error(String),
+    Shutdown, // auto-close-after-copy: ask the window to close cleanly
+
+    // History panel
+    HistoryLoaded(Vec<shared::HistoryEntry>),
+    HistoryEntryDeleted(Uuid),
 }
 
 impl DictationApp {
-    fn new(initial_text: Option<String>) -> Self {
+    fn new(initial_text: Option<String>, connect: ConnectTarget) -> Self {
         let (tx, rx) = mpsc::channel();
 
         // Start daemon communication in background thread
         let tx_clone = tx.clone();
+        let connect_clone = connect.clone();
         std::thread::spawn(move || {
-            daemon_communication_thread(tx_clone);
+            daemon_communication_thread(tx_clone, connect_clone);
         });
 
         Self {
             text: initial_text.unwrap_or_else(|| "Starting...".to_string()),
+            confirmed_segment_text: String::new(),
             complete_text: String::new(),
             recording_status: "Connecting to daemon...".to_string(),
             daemon_connected: false,
@@ -90,11 +281,22 @@ impl DictationApp {
 
             // Initialize feedback state
             audio_level: 0.0,
+            spectrum: Vec::new(),
             voice_active: false,
             is_processing: false,
+            muted_by_user: false,
+
+            // History panel state
+            show_history: false,
+            history_query: String::new(),
+            history_entries: Vec::new(),
 
             rx,
             _tx: tx,
+            connect,
+            output_backend: output::backend_for(&config::load_config().output_backend()),
+            config: config::load_config(),
+            should_close: false,
         }
     }
 
@@ -106,6 +308,7 @@ impl DictationApp {
                     if connected {
                         self.recording_status = "🔴 Recording...".to_string();
                         self.text = "".to_string();
+                        self.confirmed_segment_text = String::new();
                         self.complete_text = String::new();
                     } else {
                         self.recording_status = "Daemon not available".to_string();
@@ -116,6 +319,7 @@ impl DictationApp {
                     self.is_recording = true;
                     self.recording_status = "🔴 Recording...".to_string();
                     self.text = "".to_string();
+                    self.confirmed_segment_text = String::new();
                     self.complete_text = String::new();
                 }
                 UiMessage::RecordingStopped => {
@@ -124,11 +328,22 @@ impl DictationApp {
                     // Keep the final text for copying
                 }
                 UiMessage::TranscriptionUpdate(new_text, is_final) => {
-                    // Accumulate text chunks from daemon
-                    if !self.text.is_empty() && !new_text.trim().is_empty() {
-                        self.text.push(' ');
+                    // `new_text` is the daemon's cumulative text for the
+                    // current in-progress speech segment (see merge_partial),
+                    // not a delta - so it replaces the live segment's display
+                    // rather than being appended to it. Earlier segments
+                    // already finalized this recording are kept separately in
+                    // `confirmed_segment_text` and prefixed back on.
+                    self.text = if self.confirmed_segment_text.is_empty() {
+                        new_text.clone()
+                    } else if new_text.trim().is_empty() {
+                        self.confirmed_segment_text.clone()
+                    } else {
+                        format!("{} {}", self.confirmed_segment_text, new_text)
+                    };
+                    if is_final {
+                        self.confirmed_segment_text = self.text.clone();
                     }
-                    self.text.push_str(&new_text);
                     log::info!("Update: '{}' (final: {})", new_text, is_final);
                 }
                 UiMessage::TranscriptionComplete(final_text) => {
@@ -138,14 +353,28 @@ impl DictationApp {
                     }
                     self.complete_text.push_str(&final_text);
                     self.text = self.complete_text.clone();
+                    log::info!("Complete: '{}'", final_text);
+
+                    // In continuous mode each utterance arrives as its own
+                    // TranscriptionComplete while the session stays open; only
+                    // the one that follows Stop Recording ends it and auto-copies.
+                    if self.config.continuous() && self.is_recording {
+                        continue;
+                    }
                     self.is_recording = false;
                     self.recording_status = "Recording complete".to_string();
-                    log::info!("Complete: '{}'", final_text);
+
+                    if self.config.auto_copy() {
+                        self.copy_to_clipboard();
+                    }
                 }
                 // Real-time feedback messages
                 UiMessage::AudioLevel(level) => {
                     self.audio_level = level;
                 }
+                UiMessage::Spectrum(bands) => {
+                    self.spectrum = bands;
+                }
                 UiMessage::VoiceActivityDetected => {
                     self.voice_active = true;
                     if self.is_recording {
@@ -175,13 +404,114 @@ impl DictationApp {
                     self.text.clear();
                     self.recording_status = "Session cleared".to_string();
                 }
+                UiMessage::Reconnecting => {
+                    self.recording_status = "⚠ Reconnecting to daemon...".to_string();
+                }
+                UiMessage::RecordingSaved(path, duration_secs) => {
+                    self.recording_status = format!("💾 Saved {:.1}s to {}", duration_secs, path.display());
+                }
+                UiMessage::Muted(muted) => {
+                    self.muted_by_user = muted;
+                    if self.is_recording {
+                        self.recording_status = if muted {
+                            "🔇 Muted".to_string()
+                        } else {
+                            "🔴 Recording...".to_string()
+                        };
+                    }
+                }
                 UiMessage::Error(error) => {
                     self.recording_status = format!("Error: {}", error);
                 }
+                UiMessage::Shutdown => {
+                    self.should_close = true;
+                }
+                UiMessage::HistoryLoaded(entries) => {
+                    self.history_entries = entries;
+                }
+                UiMessage::HistoryEntryDeleted(id) => {
+                    self.history_entries.retain(|entry| entry.id != id);
+                }
+            }
+        }
+    }
+
+    /// Opens the history panel and kicks off an unfiltered search so it has
+    /// something to show immediately.
+    fn open_history(&mut self) {
+        self.show_history = true;
+        self.search_history();
+    }
+
+    /// Opens the settings window. It's a separate `eframe` app with its own
+    /// event loop (see `settings_app::SettingsApp`), so it runs as a second
+    /// `--settings` process of this same binary rather than as a window
+    /// embedded in this one.
+    fn open_settings(&self) {
+        let exe = match std::env::current_exe() {
+            Ok(exe) => exe,
+            Err(e) => {
+                log::error!("Failed to resolve current executable to open settings: {}", e);
+                return;
             }
+        };
+        if let Err(e) = std::process::Command::new(exe)
+            .arg("--settings")
+            .arg("--connect")
+            .arg(connect_addr(&self.connect))
+            .spawn()
+        {
+            log::error!("Failed to launch settings window: {}", e);
         }
     }
 
+    /// Re-runs `ListHistory` against `self.history_query` (empty means
+    /// most-recent-first) and replaces `history_entries` once the daemon replies.
+    fn search_history(&mut self) {
+        let tx = self._tx.clone();
+        let connect = self.connect.clone();
+        let query = if self.history_query.trim().is_empty() {
+            None
+        } else {
+            Some(self.history_query.clone())
+        };
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                match send_list_history(50, query, &connect).await {
+                    Ok(entries) => {
+                        let _ = tx.send(UiMessage::HistoryLoaded(entries));
+                    }
+                    Err(e) => log::error!("Failed to search history: {}", e),
+                }
+            });
+        });
+    }
+
+    fn delete_history_entry(&mut self, id: Uuid) {
+        let tx = self._tx.clone();
+        let connect = self.connect.clone();
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                match send_delete_session(id, &connect).await {
+                    Ok(()) => {
+                        let _ = tx.send(UiMessage::HistoryEntryDeleted(id));
+                    }
+                    Err(e) => log::error!("Failed to delete history entry {}: {}", id, e),
+                }
+            });
+        });
+    }
+
+    /// Loads a past dictation back into the main text area and copies it,
+    /// reusing `copy_to_clipboard` so auto-close-after-copy still applies.
+    fn copy_history_entry(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.copy_to_clipboard();
+    }
+
     fn stop_recording(&mut self) {
         if self.is_recording {
             // Immediately update UI state
@@ -189,44 +519,95 @@ impl DictationApp {
             self.recording_status = "Stopping...".to_string();
 
             // Send stop message through a new thread to avoid blocking UI
-            std::thread::spawn(|| {
-                let rt = tokio::runtime::Runtime::new().unwrap();
-                rt.block_on(async {
-                    let _ = send_stop_recording().await;
+            if let Some(session_id) = self.session_id {
+                let connect = self.connect.clone();
+                std::thread::spawn(move || {
+                    let rt = tokio::runtime::Runtime::new().unwrap();
+                    rt.block_on(async {
+                        let _ = send_stop_recording(session_id, &connect).await;
+                    });
                 });
-            });
+            }
         }
     }
 
 
+    fn toggle_mute(&mut self) {
+        let Some(session_id) = self.session_id else {
+            return;
+        };
+
+        let muted = !self.muted_by_user;
+        // Optimistically flip state; UiMessage::Muted corrects it once the
+        // daemon confirms, same as the stop-recording button does.
+        self.muted_by_user = muted;
+
+        let tx = self._tx.clone();
+        let connect = self.connect.clone();
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                match send_set_muted(session_id, muted, &connect).await {
+                    Ok(confirmed) => {
+                        let _ = tx.send(UiMessage::Muted(confirmed));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(UiMessage::Error(e.to_string()));
+                    }
+                }
+            });
+        });
+    }
+
+    fn save_audio(&mut self) {
+        let Some(session_id) = self.session_id else {
+            self.recording_status = "No session to save".to_string();
+            return;
+        };
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = std::path::PathBuf::from(format!("dictation-{}-{}.wav", session_id, timestamp));
+
+        self.recording_status = "Saving audio...".to_string();
+
+        let tx = self._tx.clone();
+        let connect = self.connect.clone();
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                match send_save_recording(session_id, path, &connect).await {
+                    Ok((path, duration_secs)) => {
+                        let _ = tx.send(UiMessage::RecordingSaved(path, duration_secs));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(UiMessage::Error(e.to_string()));
+                    }
+                }
+            });
+        });
+    }
+
     fn copy_to_clipboard(&mut self) {
         if !self.text.trim().is_empty() {
-            use std::io::Write;
-
-            // Spawn wl-copy and let it run in background (required for Wayland)
-            match std::process::Command::new("wl-copy")
-                .stdin(std::process::Stdio::piped())
-                .stdout(std::process::Stdio::null())
-                .stderr(std::process::Stdio::null())
-                .spawn()
-            {
-                Ok(mut child) => {
-                    if let Some(mut stdin) = child.stdin.take() {
-                        if stdin.write_all(self.text.as_bytes()).is_ok() {
-                            drop(stdin); // Close stdin
-                            // Don't wait for child - let wl-copy run in background
-                            self.recording_status = "📋 Copied to clipboard!".to_string();
-                            log::info!("Successfully copied to clipboard");
-                        } else {
-                            self.recording_status = "Copy failed: couldn't write to wl-copy".to_string();
-                        }
+            match self.output_backend.send(&self.text) {
+                Ok(()) => {
+                    self.recording_status = if self.output_backend.is_clipboard() {
+                        "📋 Copied to clipboard!".to_string()
                     } else {
-                        self.recording_status = "Copy failed: couldn't get stdin".to_string();
+                        "⌨ Typed into focused window!".to_string()
+                    };
+                    log::info!("Successfully sent text via {}", self.output_backend.name());
+
+                    if self.config.auto_close_after_copy() {
+                        let _ = self._tx.send(UiMessage::Shutdown);
                     }
                 }
                 Err(e) => {
                     self.recording_status = format!("Copy failed: {}", e);
-                    log::error!("Failed to spawn wl-copy: {}", e);
+                    log::error!("Failed to send text via {}: {}", self.output_backend.name(), e);
                 }
             }
         } else {
@@ -240,6 +621,11 @@ impl eframe::App for DictationApp {
         // Process any pending messages from daemon thread
         self.process_messages();
 
+        if self.should_close {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+
         // Request repaint to keep UI responsive
         ctx.request_repaint();
 
@@ -281,13 +667,18 @@ impl eframe::App for DictationApp {
 
                 // Voice activity indicator
                 if self.is_recording {
-                    let voice_color = if self.voice_active {
-                        egui::Color32::from_rgb(255, 165, 0) // Orange
+                    if self.muted_by_user {
+                        ui.colored_label(egui::Color32::GRAY, "🔇");
+                        ui.label("Muted");
                     } else {
-                        egui::Color32::GRAY
-                    };
-                    ui.colored_label(voice_color, "🎤");
-                    ui.label(if self.voice_active { "Voice" } else { "Silent" });
+                        let voice_color = if self.voice_active {
+                            egui::Color32::from_rgb(255, 165, 0) // Orange
+                        } else {
+                            egui::Color32::GRAY
+                        };
+                        ui.colored_label(voice_color, "🎤");
+                        ui.label(if self.voice_active { "Voice" } else { "Silent" });
+                    }
 
                     ui.separator();
 
@@ -299,14 +690,28 @@ impl eframe::App for DictationApp {
                 }
             });
 
-            // Audio level meter (only show when recording)
+            // Spectrum meter (only show when recording)
             if self.is_recording {
                 ui.horizontal(|ui| {
-                    ui.label("Audio Level:");
-                    let progress = egui::ProgressBar::new(self.audio_level)
-                        .desired_width(100.0)
-                        .show_percentage();
-                    ui.add(progress);
+                    ui.label("Spectrum:");
+                    let (rect, _) = ui.allocate_exact_size(
+                        egui::vec2(100.0, 16.0),
+                        egui::Sense::hover(),
+                    );
+                    let painter = ui.painter();
+                    painter.rect_filled(rect, 0.0, egui::Color32::from_gray(30));
+
+                    if !self.muted_by_user && !self.spectrum.is_empty() {
+                        let bar_width = rect.width() / self.spectrum.len() as f32;
+                        for (i, &level) in self.spectrum.iter().enumerate() {
+                            let bar_height = rect.height() * level.clamp(0.0, 1.0);
+                            let bar_rect = egui::Rect::from_min_max(
+                                egui::pos2(rect.left() + i as f32 * bar_width, rect.bottom() - bar_height),
+                                egui::pos2(rect.left() + (i as f32 + 1.0) * bar_width, rect.bottom()),
+                            );
+                            painter.rect_filled(bar_rect, 0.0, egui::Color32::from_rgb(255, 165, 0));
+                        }
+                    }
                 });
             }
 
@@ -352,14 +757,31 @@ impl eframe::App for DictationApp {
                     if ui.add_sized([120.0, 40.0], egui::Button::new("⏹ Stop Recording")).clicked() {
                         self.stop_recording();
                     }
+
+                    let mute_label = if self.muted_by_user { "🎤 Unmute" } else { "🔇 Mute" };
+                    if ui.add_sized([100.0, 40.0], egui::Button::new(mute_label)).clicked() {
+                        self.toggle_mute();
+                    }
                 } else {
                     if ui.add_sized([100.0, 40.0], egui::Button::new("📋 Copy")).clicked() {
                         self.copy_to_clipboard();
                     }
 
+                    if ui.add_sized([100.0, 40.0], egui::Button::new("💾 Save audio")).clicked() {
+                        self.save_audio();
+                    }
+
                     if ui.add_sized([100.0, 40.0], egui::Button::new("🗑 Discard")).clicked() {
                         std::process::exit(0);
                     }
+
+                    if ui.add_sized([100.0, 40.0], egui::Button::new("🕑 History")).clicked() {
+                        self.open_history();
+                    }
+
+                    if ui.add_sized([100.0, 40.0], egui::Button::new("⚙ Settings")).clicked() {
+                        self.open_settings();
+                    }
                 }
             });
 
@@ -371,24 +793,92 @@ impl eframe::App for DictationApp {
                 std::process::exit(0);
             }
         });
+
+        if self.show_history {
+            self.show_history_window(ctx);
+        }
     }
 }
 
-fn daemon_communication_thread(tx: mpsc::Sender<UiMessage>) {
+impl DictationApp {
+    fn show_history_window(&mut self, ctx: &egui::Context) {
+        let mut still_open = true;
+
+        egui::Window::new("Dictation History")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .open(&mut still_open)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Search:");
+                    let response = ui.text_edit_singleline(&mut self.history_query);
+                    let search_clicked = ui.button("🔍").clicked();
+                    if search_clicked || (response.lost_focus() && ctx.input(|i| i.key_pressed(egui::Key::Enter))) {
+                        self.search_history();
+                    }
+                });
+
+                ui.separator();
+
+                let mut to_copy = None;
+                let mut to_delete = None;
+
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        if self.history_entries.is_empty() {
+                            ui.label("No matching dictations yet.");
+                        }
+
+                        for entry in &self.history_entries {
+                            ui.group(|ui| {
+                                ui.label(&entry.text);
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("{:.1}s \u{2022} {}", entry.duration_secs, entry.model_name));
+                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                        if ui.small_button("🗑").clicked() {
+                                            to_delete = Some(entry.id);
+                                        }
+                                        if ui.small_button("📋").clicked() {
+                                            to_copy = Some(entry.text.clone());
+                                        }
+                                    });
+                                });
+                            });
+                        }
+                    });
+
+                if let Some(text) = to_copy {
+                    self.copy_history_entry(&text);
+                }
+                if let Some(id) = to_delete {
+                    self.delete_history_entry(id);
+                }
+            });
+
+        if !still_open {
+            self.show_history = false;
+        }
+    }
+}
+
+fn daemon_communication_thread(tx: mpsc::Sender<UiMessage>, connect: ConnectTarget) {
     let rt = tokio::runtime::Runtime::new().unwrap();
     rt.block_on(async {
         // Check daemon status
-        match check_daemon_status().await {
+        match check_daemon_status(&connect).await {
             Ok(true) => {
                 let _ = tx.send(UiMessage::DaemonConnected(true));
 
                 // Start recording
-                match send_start_recording().await {
+                match send_start_recording(&connect).await {
                     Ok((session_id, stream)) => {
                         let _ = tx.send(UiMessage::RecordingStarted(session_id));
 
                         // Listen for daemon messages on the same connection
-                        listen_for_daemon_messages(tx, session_id, stream).await;
+                        listen_for_daemon_messages(tx, session_id, stream, connect).await;
                     }
                     Err(e) => {
                         let _ = tx.send(UiMessage::Error(format!("Failed to start recording: {}", e)));
@@ -402,10 +892,8 @@ fn daemon_communication_thread(tx: mpsc::Sender<UiMessage>) {
     });
 }
 
-async fn check_daemon_status() -> Result<bool> {
-    let socket_path = "/tmp/dictation.sock";
-
-    match UnixStream::connect(socket_path).await {
+async fn check_daemon_status(connect: &ConnectTarget) -> Result<bool> {
+    match connect.connect().await {
         Ok(mut stream) => {
             if protocol::send_message(&mut stream, &ClientMessage::GetStatus).await.is_ok() {
                 match tokio::time::timeout(
@@ -423,9 +911,8 @@ async fn check_daemon_status() -> Result<bool> {
     }
 }
 
-async fn send_start_recording() -> Result<(Uuid, UnixStream)> {
-    let socket_path = "/tmp/dictation.sock";
-    let mut stream = UnixStream::connect(socket_path).await?;
+async fn send_start_recording(connect: &ConnectTarget) -> Result<(Uuid, Transport)> {
+    let mut stream = connect.connect().await?;
 
     protocol::send_message(&mut stream, &ClientMessage::StartRecording).await?;
 
@@ -436,63 +923,215 @@ async fn send_start_recording() -> Result<(Uuid, UnixStream)> {
     }
 }
 
-async fn send_stop_recording() -> Result<()> {
-    let socket_path = "/tmp/dictation.sock";
-    let mut stream = UnixStream::connect(socket_path).await?;
+async fn send_stop_recording(session_id: Uuid, connect: &ConnectTarget) -> Result<()> {
+    let mut stream = connect.connect().await?;
 
-    protocol::send_message(&mut stream, &ClientMessage::StopRecording).await?;
+    protocol::send_message(&mut stream, &ClientMessage::StopRecording(session_id)).await?;
     Ok(())
 }
 
+async fn send_set_muted(session_id: Uuid, muted: bool, connect: &ConnectTarget) -> Result<bool> {
+    let mut stream = connect.connect().await?;
+
+    protocol::send_message(&mut stream, &ClientMessage::SetMuted(session_id, muted)).await?;
+
+    match protocol::receive_message::<_, DaemonMessage>(&mut stream).await? {
+        DaemonMessage::Muted(confirmed) => Ok(confirmed),
+        DaemonMessage::Error(error) => anyhow::bail!("Daemon error: {}", error),
+        other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+    }
+}
+
+async fn send_save_recording(session_id: Uuid, path: std::path::PathBuf, connect: &ConnectTarget) -> Result<(std::path::PathBuf, f32)> {
+    let mut stream = connect.connect().await?;
+
+    protocol::send_message(&mut stream, &ClientMessage::SaveRecording { session_id, path }).await?;
+
+    match protocol::receive_message::<_, DaemonMessage>(&mut stream).await? {
+        DaemonMessage::RecordingSaved { path, duration_secs } => Ok((path, duration_secs)),
+        DaemonMessage::Error(error) => anyhow::bail!("Daemon error: {}", error),
+        other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+    }
+}
+
+async fn send_list_history(limit: usize, query: Option<String>, connect: &ConnectTarget) -> Result<Vec<shared::HistoryEntry>> {
+    let mut stream = connect.connect().await?;
+
+    protocol::send_message(&mut stream, &ClientMessage::ListHistory { limit, query }).await?;
+
+    match protocol::receive_message::<_, DaemonMessage>(&mut stream).await? {
+        DaemonMessage::HistoryList(entries) => Ok(entries),
+        DaemonMessage::Error(error) => anyhow::bail!("Daemon error: {}", error),
+        other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+    }
+}
+
+async fn send_delete_session(id: Uuid, connect: &ConnectTarget) -> Result<()> {
+    let mut stream = connect.connect().await?;
+
+    protocol::send_message(&mut stream, &ClientMessage::DeleteSession(id)).await?;
+
+    match protocol::receive_message::<_, DaemonMessage>(&mut stream).await? {
+        DaemonMessage::HistoryDeleted(_) => Ok(()),
+        DaemonMessage::Error(error) => anyhow::bail!("Daemon error: {}", error),
+        other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+    }
+}
+
+
+/// Tells `listen_for_daemon_messages` whether the connection ended because
+/// the daemon legitimately finished the recording (`Stopped` - exit for
+/// good) or because the connection itself dropped (`Disconnected` -
+/// reconnect and resume the same session).
+enum ListenOutcome {
+    Stopped,
+    Disconnected,
+}
 
-async fn listen_for_daemon_messages(tx: mpsc::Sender<UiMessage>, session_id: Uuid, mut stream: UnixStream) {
+async fn listen_for_daemon_messages(tx: mpsc::Sender<UiMessage>, session_id: Uuid, mut stream: Transport, connect: ConnectTarget) {
     loop {
-        match protocol::receive_message::<DaemonMessage>(&mut stream).await {
-            Ok(message) => {
-                match message {
-                    DaemonMessage::TranscriptionUpdate { session_id: msg_session_id, partial_text, is_final } => {
-                        if msg_session_id == session_id {
-                            let _ = tx.send(UiMessage::TranscriptionUpdate(partial_text, is_final));
-                        }
-                    }
-                    DaemonMessage::TranscriptionComplete(session) => {
-                        if session.id == session_id {
-                            let _ = tx.send(UiMessage::TranscriptionComplete(session.text));
-                        }
-                    }
-                    DaemonMessage::RecordingStopped => {
-                        let _ = tx.send(UiMessage::RecordingStopped);
-                        return; // Exit listen loop
-                    }
-                    // Real-time feedback messages
-                    DaemonMessage::AudioLevel(level) => {
-                        let _ = tx.send(UiMessage::AudioLevel(level));
-                    }
-                    DaemonMessage::VoiceActivityDetected => {
-                        let _ = tx.send(UiMessage::VoiceActivityDetected);
-                    }
-                    DaemonMessage::VoiceActivityEnded => {
-                        let _ = tx.send(UiMessage::VoiceActivityEnded);
-                    }
-                    DaemonMessage::ProcessingStarted => {
-                        let _ = tx.send(UiMessage::ProcessingStarted);
+        match run_daemon_message_loop(&tx, session_id, stream).await {
+            ListenOutcome::Stopped => return,
+            ListenOutcome::Disconnected => {
+                let _ = tx.send(UiMessage::DaemonConnected(false));
+                match reconnect_and_resume(&tx, session_id, &connect).await {
+                    Some(new_stream) => {
+                        let _ = tx.send(UiMessage::DaemonConnected(true));
+                        stream = new_stream;
                     }
-                    DaemonMessage::ProcessingComplete => {
-                        let _ = tx.send(UiMessage::ProcessingComplete);
+                    None => {
+                        let _ = tx.send(UiMessage::Error("Connection to daemon lost".to_string()));
+                        return;
                     }
-                    DaemonMessage::SessionCleared => {
-                        let _ = tx.send(UiMessage::SessionCleared);
+                }
+            }
+        }
+    }
+}
+
+/// Owns `stream` for as long as the connection holds up: forwards daemon
+/// messages to the UI and pings the daemon with `GetStatus` every
+/// `KEEPALIVE_INTERVAL` so a silently-dead daemon is noticed even if it isn't
+/// actively sending anything. `protocol::receive_message` isn't cancel-safe
+/// (two sequential read_exact calls), so it can't be a direct branch of the
+/// `select!` below - a keepalive tick winning the race mid-read would drop
+/// already-consumed bytes and desync the stream's framing. A background task
+/// owns the only read loop and forwards whole decoded messages through an
+/// mpsc channel, whose `recv()` is cancel-safe to select against.
+async fn run_daemon_message_loop(tx: &mpsc::Sender<UiMessage>, session_id: Uuid, stream: Transport) -> ListenOutcome {
+    let (mut read_half, mut write_half) = tokio::io::split(stream);
+    let (msg_tx, mut msg_rx) = tokio::sync::mpsc::channel::<Result<DaemonMessage>>(8);
+    tokio::spawn(async move {
+        loop {
+            let message = protocol::receive_message::<_, DaemonMessage>(&mut read_half).await;
+            let is_err = message.is_err();
+            if msg_tx.send(message).await.is_err() || is_err {
+                break;
+            }
+        }
+    });
+
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await; // first tick fires immediately; skip it so we don't ping right after connecting
+
+    loop {
+        tokio::select! {
+            message = msg_rx.recv() => {
+                match message {
+                    Some(Ok(message)) => {
+                        match message {
+                            DaemonMessage::TranscriptionUpdate { session_id: msg_session_id, partial_text, is_final } => {
+                                if msg_session_id == session_id {
+                                    let _ = tx.send(UiMessage::TranscriptionUpdate(partial_text, is_final));
+                                }
+                            }
+                            DaemonMessage::TranscriptionComplete(session) => {
+                                if session.id == session_id {
+                                    let _ = tx.send(UiMessage::TranscriptionComplete(session.text));
+                                }
+                            }
+                            DaemonMessage::RecordingStopped => {
+                                let _ = tx.send(UiMessage::RecordingStopped);
+                                return ListenOutcome::Stopped;
+                            }
+                            // Real-time feedback messages
+                            DaemonMessage::Spectrum { session_id: msg_session_id, bands } => {
+                                if msg_session_id == session_id {
+                                    let _ = tx.send(UiMessage::Spectrum(bands));
+                                }
+                            }
+                            DaemonMessage::AudioLevel(level) => {
+                                let _ = tx.send(UiMessage::AudioLevel(level));
+                            }
+                            DaemonMessage::VoiceActivityDetected => {
+                                let _ = tx.send(UiMessage::VoiceActivityDetected);
+                            }
+                            DaemonMessage::VoiceActivityEnded => {
+                                let _ = tx.send(UiMessage::VoiceActivityEnded);
+                            }
+                            DaemonMessage::ProcessingStarted => {
+                                let _ = tx.send(UiMessage::ProcessingStarted);
+                            }
+                            DaemonMessage::ProcessingComplete => {
+                                let _ = tx.send(UiMessage::ProcessingComplete);
+                            }
+                            DaemonMessage::SessionCleared => {
+                                let _ = tx.send(UiMessage::SessionCleared);
+                            }
+                            DaemonMessage::Error(error) => {
+                                let _ = tx.send(UiMessage::Error(error));
+                            }
+                            _ => {}
+                        }
                     }
-                    DaemonMessage::Error(error) => {
-                        let _ = tx.send(UiMessage::Error(error));
+                    Some(Err(e)) => {
+                        log::debug!("Connection lost mid-recording: {}", e);
+                        return ListenOutcome::Disconnected;
                     }
-                    _ => {}
+                    None => return ListenOutcome::Disconnected,
                 }
             }
-            Err(e) => {
-                let _ = tx.send(UiMessage::Error(format!("Connection lost: {}", e)));
-                return;
+            _ = keepalive.tick() => {
+                if protocol::send_message(&mut write_half, &ClientMessage::GetStatus).await.is_err() {
+                    log::debug!("Keepalive ping failed, treating daemon as disconnected");
+                    return ListenOutcome::Disconnected;
+                }
             }
         }
     }
+}
+
+/// Retries `connect` with bounded exponential backoff (100ms doubling up to
+/// 5s, plus up to 50ms of jitter so multiple popups reconnecting at once
+/// don't all retry in lockstep) until the daemon comes back, then
+/// re-attaches to `session_id` via `ClientMessage::ResumeSession` so
+/// streaming can continue without losing the text buffered so far. Gives up
+/// only if the daemon reports the session itself is gone.
+async fn reconnect_and_resume(tx: &mpsc::Sender<UiMessage>, session_id: Uuid, connect: &ConnectTarget) -> Option<Transport> {
+    let mut backoff = Duration::from_millis(100);
+    const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+    loop {
+        let _ = tx.send(UiMessage::Reconnecting);
+
+        if let Ok(mut stream) = connect.connect().await {
+            if protocol::send_message(&mut stream, &ClientMessage::ResumeSession(session_id)).await.is_ok() {
+                match protocol::receive_message::<_, DaemonMessage>(&mut stream).await {
+                    Ok(DaemonMessage::SessionResumed(_)) => return Some(stream),
+                    Ok(DaemonMessage::Error(error)) => {
+                        let _ = tx.send(UiMessage::Error(format!("Failed to resume session: {}", error)));
+                        return None;
+                    }
+                    _ => {} // Unexpected reply - fall through and retry
+                }
+            }
+        }
+
+        let jitter_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_millis() % 50)
+            .unwrap_or(0);
+        tokio::time::sleep(backoff + Duration::from_millis(jitter_ms as u64)).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
 }
\ No newline at end of file
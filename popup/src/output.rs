@@ -0,0 +1,138 @@
+// Pluggable destinations for a finished dictation. The popup used to shell
+// out to wl-copy unconditionally, which breaks on X11, macOS, and headless
+// setups, and only ever copies - it can never type into the focused window.
+// `OutputBackend` lets `config.output.backend` pick a clipboard tool per
+// platform or switch to direct typing instead.
+
+use anyhow::{anyhow, bail, Result};
+use std::io::Write;
+use std::process::{Command, ExitStatus, Stdio};
+
+/// A destination a finished dictation can be sent to. `is_clipboard` tells
+/// the caller whether to fire the "copied to clipboard" notification
+/// afterwards - direct-typing backends never touch the clipboard.
+pub trait OutputBackend {
+    fn name(&self) -> &'static str;
+    fn is_clipboard(&self) -> bool;
+    fn send(&self, text: &str) -> Result<()>;
+}
+
+/// Spawns `program` with `args` and pipes `text` to its stdin, then lets it
+/// keep running in the background rather than waiting on it - clipboard
+/// daemons like wl-copy/xclip serve paste requests for as long as they stay
+/// alive, the same way the old hardcoded `wl-copy` call did.
+fn pipe_to(program: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to spawn {}: {}", program, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Runs `program` with `args`, waiting for it to exit, and returns its exit
+/// status; only a failure to spawn is an `Err`, so callers check
+/// `status.success()` themselves - used by the direct-typing backends, which
+/// need to finish before the popup can report success.
+fn run(program: &str, args: &[&str]) -> Result<ExitStatus> {
+    Command::new(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| anyhow!("Failed to spawn {}: {}", program, e))
+}
+
+pub struct WaylandClipboard;
+
+impl OutputBackend for WaylandClipboard {
+    fn name(&self) -> &'static str {
+        "wl-copy"
+    }
+
+    fn is_clipboard(&self) -> bool {
+        true
+    }
+
+    fn send(&self, text: &str) -> Result<()> {
+        pipe_to("wl-copy", &[], text)
+    }
+}
+
+pub struct X11Clipboard;
+
+impl OutputBackend for X11Clipboard {
+    fn name(&self) -> &'static str {
+        "xclip/xsel"
+    }
+
+    fn is_clipboard(&self) -> bool {
+        true
+    }
+
+    fn send(&self, text: &str) -> Result<()> {
+        pipe_to("xclip", &["-selection", "clipboard"], text)
+            .or_else(|_| pipe_to("xsel", &["--clipboard", "--input"], text))
+    }
+}
+
+pub struct MacClipboard;
+
+impl OutputBackend for MacClipboard {
+    fn name(&self) -> &'static str {
+        "pbcopy"
+    }
+
+    fn is_clipboard(&self) -> bool {
+        true
+    }
+
+    fn send(&self, text: &str) -> Result<()> {
+        pipe_to("pbcopy", &[], text)
+    }
+}
+
+/// Types the text into whatever window currently has focus instead of
+/// copying it, preferring `wtype` (Wayland-native) and falling back to
+/// `ydotool` (works under Wayland and X11 alike, but needs its daemon
+/// already running).
+pub struct DirectType;
+
+impl OutputBackend for DirectType {
+    fn name(&self) -> &'static str {
+        "wtype/ydotool"
+    }
+
+    fn is_clipboard(&self) -> bool {
+        false
+    }
+
+    fn send(&self, text: &str) -> Result<()> {
+        match run("wtype", &[text]) {
+            Ok(status) if status.success() => Ok(()),
+            _ => match run("ydotool", &["type", text]) {
+                Ok(status) if status.success() => Ok(()),
+                Ok(status) => bail!("ydotool exited with {}", status),
+                Err(e) => Err(e),
+            },
+        }
+    }
+}
+
+/// Picks a backend from `config.output_backend()` ("wayland", "x11",
+/// "macos", "type"), falling back to `WaylandClipboard` to match this
+/// popup's previous hardcoded behavior for an unset or unrecognized value.
+pub fn backend_for(name: &str) -> Box<dyn OutputBackend> {
+    match name {
+        "x11" => Box::new(X11Clipboard),
+        "macos" => Box::new(MacClipboard),
+        "type" => Box::new(DirectType),
+        _ => Box::new(WaylandClipboard),
+    }
+}
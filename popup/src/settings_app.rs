@@ -1,7 +1,11 @@
 use eframe::egui;
-use crate::config::{Config, load_config, save_config, UIConfig, WhisperConfig};
+use cpal::traits::{DeviceTrait, HostTrait};
+use shared::ipc::{protocol, ClientMessage, DaemonMessage};
+use shared::transport::ConnectTarget;
+use crate::config::{AudioConfig, Config, load_config, save_config, OutputConfig, UIConfig, WhisperConfig};
 
 pub struct SettingsApp {
+    connect: ConnectTarget,
     config: Config,
     model: String,
     timeout: f32,
@@ -9,10 +13,20 @@ pub struct SettingsApp {
     fuzzy_match_threshold: f32,
     auto_copy: bool,
     auto_close_after_copy: bool,
+    use_gpu: bool,
+    gpu_device: u32,
+    denoise: bool,
+    denoise_strength: f32,
+    save_recordings: bool,
+    recordings_dir: String,
+    device: Option<String>,
+    available_devices: Vec<String>,
+    output_backend: String,
+    continuous: bool,
 }
 
 impl SettingsApp {
-    pub fn new() -> Self {
+    pub fn new(connect: ConnectTarget) -> Self {
         let config = load_config();
         let model = config.model();
         let timeout = config.model_timeout_seconds() as f32;
@@ -20,8 +34,19 @@ impl SettingsApp {
         let fuzzy_match_threshold = config.fuzzy_match_threshold();
         let auto_copy = config.auto_copy();
         let auto_close_after_copy = config.auto_close_after_copy();
+        let use_gpu = config.use_gpu();
+        let gpu_device = config.gpu_device();
+        let denoise = config.denoise();
+        let denoise_strength = config.denoise_strength();
+        let save_recordings = config.save_recordings();
+        let recordings_dir = config.recordings_dir();
+        let device = config.device();
+        let available_devices = list_input_devices();
+        let output_backend = config.output_backend();
+        let continuous = config.continuous();
 
         Self {
+            connect,
             config,
             model,
             timeout,
@@ -29,6 +54,45 @@ impl SettingsApp {
             fuzzy_match_threshold,
             auto_copy,
             auto_close_after_copy,
+            use_gpu,
+            gpu_device,
+            denoise,
+            denoise_strength,
+            save_recordings,
+            recordings_dir,
+            device,
+            available_devices,
+            output_backend,
+            continuous,
+        }
+    }
+}
+
+/// Tells the running daemon to re-read its config file and apply the
+/// settings that support hot-reload, so a saved change takes effect without
+/// restarting the daemon.
+async fn send_reload_config(connect: &ConnectTarget) -> anyhow::Result<()> {
+    let mut stream = connect.connect().await?;
+    protocol::send_message(&mut stream, &ClientMessage::ReloadConfig).await?;
+    match protocol::receive_message::<_, DaemonMessage>(&mut stream).await? {
+        DaemonMessage::ConfigReloaded => Ok(()),
+        DaemonMessage::Error(error) => anyhow::bail!("Daemon error: {}", error),
+        other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+    }
+}
+
+/// Enumerates input device names for the device-selection dropdown. The
+/// popup doesn't depend on the client crate's `AudioCapture`, so this talks
+/// to cpal directly, the same way `client::audio::AudioCapture::list_input_devices`
+/// does. Falls back to an empty list (leaving only "Default device")
+/// if cpal can't enumerate anything.
+fn list_input_devices() -> Vec<String> {
+    let host = cpal::default_host();
+    match host.input_devices() {
+        Ok(devices) => devices.filter_map(|d| d.name().ok()).collect(),
+        Err(e) => {
+            log::warn!("Failed to enumerate audio input devices: {}", e);
+            Vec::new()
         }
     }
 }
@@ -79,6 +143,75 @@ impl eframe::App for SettingsApp {
                 ui.add(egui::Slider::new(&mut self.fuzzy_match_threshold, 0.5..=1.0).text("similarity"));
             });
 
+            ui.add_space(10.0);
+
+            ui.checkbox(&mut self.use_gpu, "Use GPU acceleration (CUDA/Metal)")
+                .on_hover_text("Falls back to CPU automatically if the model fails to load on the selected GPU");
+            if self.use_gpu {
+                ui.horizontal(|ui| {
+                    ui.label("GPU device:");
+                    ui.add(egui::DragValue::new(&mut self.gpu_device).range(0..=8))
+                        .on_hover_text("Index into the system's list of GPU devices; 0 is almost always correct on single-GPU machines");
+                });
+            }
+
+            ui.add_space(10.0);
+
+            ui.checkbox(&mut self.denoise, "Reduce background noise before transcription")
+                .on_hover_text("Learns a noise profile from leading silence, then gates out magnitudes near it before transcribing");
+            if self.denoise {
+                ui.horizontal(|ui| {
+                    ui.label("Denoise strength:");
+                    ui.add(egui::Slider::new(&mut self.denoise_strength, 0.0..=1.0))
+                        .on_hover_text("0.0 is mildest (only gates what sits below the learned noise level), 1.0 is most aggressive - too high can clip quiet speech along with noise");
+                });
+            }
+
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Input device:");
+                let selected_label = self.device.clone().unwrap_or_else(|| "Default device".to_string());
+                egui::ComboBox::from_id_salt("input_device")
+                    .selected_text(selected_label)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.device, None, "Default device");
+                        for name in self.available_devices.clone() {
+                            ui.selectable_value(&mut self.device, Some(name.clone()), name);
+                        }
+                    });
+            });
+
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Output:");
+                egui::ComboBox::from_id_salt("output_backend")
+                    .selected_text(self.output_backend.clone())
+                    .show_ui(ui, |ui| {
+                        for option in ["wayland", "x11", "macos", "type"] {
+                            ui.selectable_value(&mut self.output_backend, option.to_string(), option);
+                        }
+                    });
+            });
+
+            ui.add_space(10.0);
+
+            ui.checkbox(&mut self.continuous, "Continuous hands-free dictation (auto-segment on silence)")
+                .on_hover_text("Each pause emits its own transcript and the session stays open for the next utterance - auto-copy waits until you click Stop Recording");
+
+            ui.add_space(10.0);
+
+            ui.checkbox(&mut self.save_recordings, "Save recordings and transcripts to disk")
+                .on_hover_text("Also required for --retranscribe, which re-runs Whisper over the archived WAV");
+            if self.save_recordings {
+                ui.horizontal(|ui| {
+                    ui.label("Recordings folder:");
+                    ui.text_edit_singleline(&mut self.recordings_dir)
+                        .on_hover_text("Relative paths are resolved against the daemon's working directory, not the popup's");
+                });
+            }
+
             ui.add_space(10.0);
             ui.separator();
             ui.add_space(10.0);
@@ -106,6 +239,11 @@ impl eframe::App for SettingsApp {
                             model_timeout_seconds: Some(self.timeout as u32),
                             language: Some(self.language.clone()),
                             fuzzy_match_threshold: Some(self.fuzzy_match_threshold),
+                            use_gpu: Some(self.use_gpu),
+                            gpu_device: Some(self.gpu_device),
+                            denoise: Some(self.denoise),
+                            denoise_strength: Some(self.denoise_strength),
+                            continuous: Some(self.continuous),
                         });
                     } else {
                         let whisper_config = new_config.whisper.as_mut().unwrap();
@@ -113,6 +251,11 @@ impl eframe::App for SettingsApp {
                         whisper_config.model_timeout_seconds = Some(self.timeout as u32);
                         whisper_config.language = Some(self.language.clone());
                         whisper_config.fuzzy_match_threshold = Some(self.fuzzy_match_threshold);
+                        whisper_config.use_gpu = Some(self.use_gpu);
+                        whisper_config.gpu_device = Some(self.gpu_device);
+                        whisper_config.denoise = Some(self.denoise);
+                        whisper_config.denoise_strength = Some(self.denoise_strength);
+                        whisper_config.continuous = Some(self.continuous);
                     }
 
                     // Update UI config
@@ -127,6 +270,24 @@ impl eframe::App for SettingsApp {
                         ui_config.auto_close_after_copy = Some(self.auto_close_after_copy);
                     }
 
+                    // Update audio config
+                    if new_config.audio.is_none() {
+                        new_config.audio = Some(AudioConfig {
+                            device: self.device.clone(),
+                            save_recordings: Some(self.save_recordings),
+                            recordings_dir: Some(self.recordings_dir.clone()),
+                        });
+                    } else {
+                        let audio_config = new_config.audio.as_mut().unwrap();
+                        audio_config.device = self.device.clone();
+                        audio_config.save_recordings = Some(self.save_recordings);
+                        audio_config.recordings_dir = Some(self.recordings_dir.clone());
+                    }
+
+                    new_config.output = Some(OutputConfig {
+                        backend: Some(self.output_backend.clone()),
+                    });
+
                     match save_config(&new_config) {
                         Ok(_) => {
                             // Show success notification
@@ -139,11 +300,13 @@ impl eframe::App for SettingsApp {
                             self.config = new_config;
                             log::info!("Settings saved successfully");
 
-                            // Tell daemon to reload config
-                            std::thread::spawn(|| {
+                            // Tell the daemon to pick up the new settings without
+                            // restarting it.
+                            let connect = self.connect.clone();
+                            std::thread::spawn(move || {
                                 let rt = tokio::runtime::Runtime::new().unwrap();
                                 rt.block_on(async {
-                                    if let Err(e) = crate::daemon_comm::send_reload_config().await {
+                                    if let Err(e) = send_reload_config(&connect).await {
                                         log::error!("Failed to send reload config: {}", e);
                                     }
                                 });
@@ -9,6 +9,8 @@ pub struct Config {
     pub audio: AudioConfig,
     pub ui: UiConfig,
     pub ipc: IpcConfig,
+    pub metrics: MetricsConfig,
+    pub history: HistoryConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,7 +18,17 @@ pub struct WhisperConfig {
     pub model_path: PathBuf,
     pub model_timeout_seconds: u64,
     pub vad_threshold: f32,
+    pub vad_sensitivity: f32,
+    pub silence_timeout_ms: u64, // How long the client waits after speech ends before auto-stopping
     pub language: Option<String>,
+    pub use_gpu: bool,  // Offload inference to a CUDA/Metal-capable whisper.cpp backend
+    pub gpu_device: u32, // Which GPU to use when use_gpu is set and more than one is available
+    pub streaming: bool, // Emit incremental partials from VAD segment boundaries instead of only transcribing once on stop
+    pub denoise: bool, // Spectrally gate out steady background noise before transcription
+    pub denoise_strength: f32, // 0.0 (mildest: gates only bins below the noise floor) to 1.0 (aggressive: up to 4x the floor); how far above the noise floor a bin must sit to pass through
+    pub continuous: bool, // Finalize each VAD segment as its own TranscriptionComplete and keep the session open for the next utterance, instead of requiring an explicit StopRecording
+    pub silence_ms: u64, // How long frames must read as non-speech (energy below threshold, or a hiss-like zero-crossing rate) before a segment is finalized
+    pub energy_threshold: f32, // Extra absolute floor added on top of the sensitivity-scaled noise floor; 0.0 leaves sensitivity as the sole knob
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +37,8 @@ pub struct AudioConfig {
     pub sample_rate: u32,
     pub channels: u16,
     pub buffer_size: usize,
+    pub save_recordings: bool, // Archive each finished session's audio + transcript to recordings_dir
+    pub recordings_dir: PathBuf,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +53,25 @@ pub struct UiConfig {
 pub struct IpcConfig {
     pub socket_path: PathBuf,
     pub timeout_seconds: u64,
+    // `host:port` to also listen for TCP connections on, for a client
+    // connecting via `--connect tcp://host:port` (shared::transport::ConnectTarget::Tcp)
+    // from another machine. None means TCP is disabled and only the Unix
+    // socket accepts connections.
+    pub tcp_bind: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    pub enabled: bool, // Only takes effect when the daemon is built with the `metrics` feature
+    pub pushgateway_url: String,
+    pub push_interval_seconds: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    pub enabled: bool, // Persist each completed session to the history database under config_dir()
+    pub max_entries: usize, // Oldest rows beyond this count are pruned after each insert; 0 means unbounded
+    pub retention_days: u32, // Rows older than this are pruned after each insert; 0 means unbounded
 }
 
 impl Default for Config {
@@ -48,13 +81,25 @@ impl Default for Config {
                 model_path: PathBuf::from("models/ggml-base.en.bin"),
                 model_timeout_seconds: 300, // 5 minutes
                 vad_threshold: 0.1,
+                vad_sensitivity: 0.5,
+                silence_timeout_ms: 1500,
                 language: Some("en".to_string()),
+                use_gpu: false,
+                gpu_device: 0,
+                streaming: false,
+                denoise: false,
+                denoise_strength: 0.5,
+                continuous: false,
+                silence_ms: 700,
+                energy_threshold: 0.0,
             },
             audio: AudioConfig {
                 device: None, // Use default device
                 sample_rate: 16000,
                 channels: 1,
                 buffer_size: 1024,
+                save_recordings: false,
+                recordings_dir: PathBuf::from("recordings"),
             },
             ui: UiConfig {
                 popup_width: 400,
@@ -65,6 +110,17 @@ impl Default for Config {
             ipc: IpcConfig {
                 socket_path: PathBuf::from("/tmp/dictation.sock"),
                 timeout_seconds: 30,
+                tcp_bind: None,
+            },
+            metrics: MetricsConfig {
+                enabled: false,
+                pushgateway_url: String::from("http://localhost:9091"),
+                push_interval_seconds: 15,
+            },
+            history: HistoryConfig {
+                enabled: true,
+                max_entries: 1000,
+                retention_days: 90,
             },
         }
     }
@@ -1,15 +1,26 @@
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use uuid::Uuid;
-use crate::types::{TranscriptionSession, AudioChunk};
+use crate::types::{TranscriptionSession, AudioChunk, OpusChunk, HistoryEntry};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ClientMessage {
     StartRecording,
-    StopRecording,
+    StopRecording(Uuid),      // Finalize and remove only this session
     StreamAudio(AudioChunk),
+    StreamAudioOpus(OpusChunk), // Opus-encoded alternative to StreamAudio; only sent once GetStatus confirms opus_supported
+    ResumeSession(Uuid), // Re-attach to a session after a dropped/reconnected transport
     GetStatus,
-    ClearSession,        // Clear any buffered/old transcriptions
+    ClearSession(Uuid),  // Drop a session's buffered audio/transcription
     SetSensitivity(f32), // Adjust voice detection sensitivity (0.0-1.0)
+    SaveRecording { session_id: Uuid, path: PathBuf }, // Write the session's raw PCM to disk as WAV
+    SetMuted(Uuid, bool), // While muted, incoming audio for this session is dropped before it reaches VAD/transcription
+    ReloadConfig, // Re-read the config file from disk and apply settings that support hot-reload (e.g. audio.device)
+    Subscribe(Uuid), // Check a session exists before treating its broadcast TranscriptionUpdate/TranscriptionComplete as relevant; every connection already receives every session's events and filters by session_id
+    ListHistory { limit: usize, query: Option<String> }, // Full-text search over archived transcripts; None lists most-recent-first
+    GetSession(Uuid), // Fetch one archived history entry by id (distinct from an in-memory TranscriptionSession)
+    DeleteSession(Uuid), // Remove one archived history entry; does not touch an in-progress session of the same id
+    Retranscribe(Uuid), // Re-run WhisperManager::retranscribe over the WAV archived by this session id under config.audio.recordings_dir
     Shutdown,
 }
 
@@ -17,6 +28,8 @@ pub enum ClientMessage {
 pub enum DaemonMessage {
     RecordingStarted(Uuid),
     RecordingStopped,
+    SessionResumed(Uuid), // Confirms ResumeSession re-attached to a still-live session
+    Subscribed(Uuid),     // Confirms the session named by Subscribe exists
 
     // Enhanced transcription messages
     TranscriptionUpdate {
@@ -28,15 +41,30 @@ pub enum DaemonMessage {
 
     // Real-time feedback
     AudioLevel(f32),           // Current audio level (0.0-1.0)
+    Spectrum { session_id: Uuid, bands: Vec<f32> }, // Per-band magnitude levels (0.0-1.0) for a spectrum meter, pushed via a dedicated high-rate broadcast separate from model_events
     VoiceActivityDetected,     // Voice detected, processing will start
     VoiceActivityEnded,        // Voice stopped, finishing segment
     ProcessingStarted,         // Started transcribing audio chunk
     ProcessingComplete,        // Finished transcribing chunk
 
+    // Model lifecycle
+    ModelLoading,              // Whisper model load has started
+    ModelLoaded,               // Whisper model is ready
+    ModelUnloaded,             // Whisper model was released after sitting idle
+
+    Muted(bool),               // Confirms SetMuted took effect
+    ConfigReloaded,            // Confirms ReloadConfig was applied
+
     // Status and session management
     Error(String),
     Status(DaemonStatus),
     SessionCleared,            // Confirm session was cleared
+    RecordingSaved { path: PathBuf, duration_secs: f32 }, // Confirms SaveRecording wrote a file
+
+    HistoryList(Vec<HistoryEntry>),        // Response to ListHistory
+    HistorySession(Option<HistoryEntry>),  // Response to GetSession; None if no history row with that id
+    HistoryDeleted(Uuid),                  // Confirms DeleteSession removed (or found nothing for) that id
+    Retranscribed { session_id: Uuid, text: String }, // Response to Retranscribe
 }
 
 
@@ -48,16 +76,16 @@ pub struct DaemonStatus {
     pub audio_device: String,      // Current audio device name
     pub buffer_size: usize,        // Current audio buffer size
     pub vad_sensitivity: f32,      // Voice detection sensitivity (0.0-1.0)
+    pub opus_supported: bool,      // Whether this daemon understands StreamAudioOpus
 }
 
 pub mod protocol {
     use super::*;
     use anyhow::Result;
-    use tokio::net::UnixStream;
-    use tokio::io::{AsyncReadExt, AsyncWriteExt};
-    
-    pub async fn send_message<T: Serialize>(
-        stream: &mut UnixStream,
+    use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+    pub async fn send_message<W: AsyncWrite + Unpin, T: Serialize>(
+        stream: &mut W,
         message: &T
     ) -> Result<()> {
         let serialized = rmp_serde::to_vec(message)?;
@@ -70,8 +98,8 @@ pub mod protocol {
         Ok(())
     }
 
-    pub async fn receive_message<T: for<'de> Deserialize<'de>>(
-        stream: &mut UnixStream
+    pub async fn receive_message<R: AsyncRead + Unpin, T: for<'de> Deserialize<'de>>(
+        stream: &mut R
     ) -> Result<T> {
         let mut len_bytes = [0u8; 4];
         stream.read_exact(&mut len_bytes).await?;
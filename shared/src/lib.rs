@@ -1,5 +1,6 @@
 pub mod config;
 pub mod ipc;
+pub mod transport;
 pub mod types;
 
 pub use config::*;
@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpStream, UnixStream};
+
+/// Where to reach the daemon: a Unix domain socket path, or `tcp://host:port`
+/// for a daemon running on another machine. Lets the GUI run on one box while
+/// the audio-capture/transcription daemon runs on another. Defaults to the
+/// Unix socket when no `--connect` address is given.
+#[derive(Debug, Clone)]
+pub enum ConnectTarget {
+    Unix(PathBuf),
+    Tcp(String),
+}
+
+impl ConnectTarget {
+    pub fn parse(addr: &str) -> Self {
+        match addr.strip_prefix("tcp://") {
+            Some(host_port) => ConnectTarget::Tcp(host_port.to_string()),
+            None => ConnectTarget::Unix(PathBuf::from(addr)),
+        }
+    }
+
+    pub fn unix_default(socket_path: PathBuf) -> Self {
+        ConnectTarget::Unix(socket_path)
+    }
+
+    pub async fn connect(&self) -> Result<Transport> {
+        match self {
+            ConnectTarget::Unix(path) => {
+                let stream = UnixStream::connect(path)
+                    .await
+                    .with_context(|| format!("Failed to connect to Unix socket at {:?}", path))?;
+                Ok(Transport::Unix(stream))
+            }
+            ConnectTarget::Tcp(addr) => {
+                let stream = TcpStream::connect(addr)
+                    .await
+                    .with_context(|| format!("Failed to connect to {}", addr))?;
+                Ok(Transport::Tcp(stream))
+            }
+        }
+    }
+}
+
+/// A daemon connection over either transport. Implements `AsyncRead`/`AsyncWrite`
+/// so it works transparently with `protocol::send_message`/`receive_message`.
+pub enum Transport {
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl AsyncRead for Transport {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Transport::Unix(s) => Pin::new(s).poll_read(cx, buf),
+            Transport::Tcp(s) => Pin::new(s).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Transport {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Transport::Unix(s) => Pin::new(s).poll_write(cx, buf),
+            Transport::Tcp(s) => Pin::new(s).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Transport::Unix(s) => Pin::new(s).poll_flush(cx),
+            Transport::Tcp(s) => Pin::new(s).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Transport::Unix(s) => Pin::new(s).poll_shutdown(cx),
+            Transport::Tcp(s) => Pin::new(s).poll_shutdown(cx),
+        }
+    }
+}
@@ -27,6 +27,18 @@ pub struct AudioChunk {
     pub timestamp: std::time::SystemTime,
 }
 
+/// Opus-encoded equivalent of `AudioChunk`. `frames` holds one or more 20ms
+/// Opus packets (encoded at `sample_rate`/`channels`) in playback order;
+/// batching frames lets a sender coalesce several packets per IPC message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpusChunk {
+    pub session_id: Uuid,
+    pub frames: Vec<Vec<u8>>,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub timestamp: std::time::SystemTime,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelInfo {
     pub name: String,
@@ -35,6 +47,18 @@ pub struct ModelInfo {
     pub last_used: Option<std::time::SystemTime>,
 }
 
+/// A completed `TranscriptionSession`, persisted to the history database so it
+/// can be recalled after the session that produced it is gone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: Uuid,
+    pub text: String,
+    pub confidence: Option<f32>,
+    pub created_at: std::time::SystemTime,
+    pub duration_secs: f32,
+    pub model_name: String,
+}
+
 impl TranscriptionSession {
     pub fn new() -> Self {
         Self {